@@ -0,0 +1,44 @@
+//! the command line every day binary shares. days with flags of their own
+//! `#[command(flatten)]` [`InputArgs`] into their own parser.
+
+pub use clap::Parser;
+
+/// which input to read. on its own it's everything `main!` takes
+#[derive(Parser, Clone, Debug, Default)]
+#[command(about = "print both parts of the puzzle")]
+pub struct InputArgs {
+    /// a name like `2` for `data/N.2.txt`, a path, or `-` for stdin
+    pub input: Option<String>,
+}
+
+impl InputArgs {
+    pub fn read(&self, day: u8) -> std::io::Result<Vec<String>> {
+        crate::read_input(day, self.input.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Args;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Day {
+        #[command(flatten)]
+        input: InputArgs,
+        #[arg(long)]
+        strict: bool,
+    }
+
+    #[test]
+    fn test_flattened_input() {
+        let day = Day::parse_from(["day", "--strict", "friend"]);
+        assert_eq!(day.input.input.as_deref(), Some("friend"));
+        assert!(day.strict);
+        assert_eq!(InputArgs::parse_from(["day"]).input, None);
+        assert!(InputArgs::augment_args(clap::Command::new("day"))
+            .try_get_matches_from(["day", "a", "b"])
+            .is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! parse errors that know where they happened, so `main` can point at the bad
+//! part of the input instead of panicking somewhere inside an `unwrap()`
+
+use std::{convert::Infallible, fmt, ops::Range};
+
+/// anything that can be pinned to a spot in the input
+pub trait Diagnostic {
+    /// 0-based line of the input
+    fn line(&self) -> usize;
+    /// byte columns of the offending token on that line
+    fn columns(&self) -> Range<usize>;
+    /// what the parser wanted to find there
+    fn expected(&self) -> String;
+}
+
+/// a parse error for one day. `K` is that day's enum of things it can expect,
+/// and its `Display` is the expected token ("a cube count", "`|`"...)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError<K> {
+    pub line: usize,
+    pub columns: Range<usize>,
+    pub kind: K,
+}
+
+impl<K> ParseError<K> {
+    /// an error on the first line; callers that know better move it with `offset`
+    pub fn new(kind: K, columns: Range<usize>) -> Self {
+        Self {
+            line: 0,
+            columns,
+            kind,
+        }
+    }
+
+    /// move the error down `lines` lines and right `columns` bytes, for when a
+    /// piece of the input was parsed on its own
+    pub fn offset(mut self, lines: usize, columns: usize) -> Self {
+        self.line += lines;
+        self.columns = (self.columns.start + columns)..(self.columns.end + columns);
+        self
+    }
+}
+
+impl<K: fmt::Display> Diagnostic for ParseError<K> {
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn columns(&self) -> Range<usize> {
+        self.columns.clone()
+    }
+
+    fn expected(&self) -> String {
+        self.kind.to_string()
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line + 1,
+            self.columns.start + 1,
+            self.kind
+        )
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> std::error::Error for ParseError<K> {}
+
+/// for days whose parser can't fail
+impl Diagnostic for Infallible {
+    fn line(&self) -> usize {
+        match *self {}
+    }
+
+    fn columns(&self) -> Range<usize> {
+        match *self {}
+    }
+
+    fn expected(&self) -> String {
+        match *self {}
+    }
+}
+
+/// render an error as the offending input line with the bad span underlined
+///
+/// ```text
+/// error: expected a cube count
+///  --> line 3, column 9
+///   |
+/// 3 | Game 3: x green, 6 blue
+///   |         ^
+/// ```
+pub fn render(error: &impl Diagnostic, lines: &[String]) -> String {
+    let heading = format!("error: expected {}", error.expected());
+    annotate(&heading, error.line(), error.columns(), lines)
+}
+
+/// `heading` over input line `line` (0-based) with `columns` underlined, the
+/// way `render` draws errors. for warnings and the like that aren't errors
+pub fn annotate(heading: &str, line: usize, columns: Range<usize>, lines: &[String]) -> String {
+    let line_number = (line + 1).to_string();
+    let gutter = " ".repeat(line_number.len());
+    let source = lines.get(line).map(|l| l.as_str()).unwrap_or("");
+    let start = columns.start.min(source.len());
+    // always draw at least one caret, even for "expected something here" at the end of a line
+    let width = columns.end.min(source.len()).saturating_sub(start).max(1);
+    format!(
+        "{heading}\n{gutter}--> line {line_number}, column {column}\n{gutter} |\n{line_number} | {source}\n{gutter} | {pad}{carets}",
+        column = columns.start + 1,
+        pad = " ".repeat(source[..start].chars().count()),
+        carets = "^".repeat(width),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Number;
+
+    impl fmt::Display for Number {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a number")
+        }
+    }
+
+    #[test]
+    fn test_render_points_at_span() {
+        let lines: Vec<String> = vec!["seeds: 1 2".into(), "50 x8 2".into()];
+        let e = ParseError::new(Number, 0..2).offset(1, 3);
+        assert_eq!(e.to_string(), "line 2, column 4: expected a number");
+        assert_eq!(
+            render(&e, &lines),
+            "error: expected a number\n --> line 2, column 4\n  |\n2 | 50 x8 2\n  |    ^^"
+        );
+    }
+
+    #[test]
+    fn test_annotate() {
+        let lines: Vec<String> = vec!["50 98 2".into()];
+        assert_eq!(
+            annotate("warning: odd", 0, 3..5, &lines),
+            "warning: odd\n --> line 1, column 4\n  |\n1 | 50 98 2\n  |    ^^"
+        );
+    }
+
+    #[test]
+    fn test_render_past_end_of_line() {
+        let lines: Vec<String> = vec!["Card 1".into()];
+        let e = ParseError::new(Number, 6..6);
+        assert!(render(&e, &lines).ends_with("1 | Card 1\n  |       ^"));
+    }
+}
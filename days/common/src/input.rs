@@ -0,0 +1,168 @@
+//! finding the puzzle input for a day.
+//!
+//! the input argument can be
+//! - nothing: `data/N.1.txt`
+//! - `-`: read stdin
+//! - a path to an existing file
+//! - a name: `2` picks `data/N.2.txt`, `friend` picks `data/N.friend.txt`
+//!
+//! `data/` is `$AOC_DATA_DIR` if set, otherwise the `data` directory next to the
+//! workspace `Cargo.toml`, found by walking up from the current directory. that
+//! way `cargo run -p day05` works from inside `days/day05` too.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+/// the `data` directory to read named inputs from
+pub fn data_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+    Ok(workspace_root()?.join("data"))
+}
+
+/// the directory holding the workspace `Cargo.toml`, looked up from the current directory
+pub fn workspace_root() -> io::Result<PathBuf> {
+    let cwd = env::current_dir()?;
+    find_workspace_root(&cwd).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no workspace Cargo.toml above {}, set {DATA_DIR_VAR} to the data directory",
+                cwd.display()
+            ),
+        )
+    })
+}
+
+/// walk up from `start` looking for the `Cargo.toml` that declares the workspace
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        manifest
+            .lines()
+            .any(|l| l.trim() == "[workspace]")
+            .then(|| dir.to_path_buf())
+    })
+}
+
+/// work out where `input` points without touching the data directory unless it
+/// has to
+pub fn resolve(day: u8, input: Option<&str>) -> io::Result<Source> {
+    match input {
+        Some("-") => Ok(Source::Stdin),
+        Some(path) if Path::new(path).is_file() => Ok(Source::File(PathBuf::from(path))),
+        Some(name) => resolve_in(day, name, &data_dir()?),
+        None => resolve_in(day, "1", &data_dir()?),
+    }
+}
+
+/// a named input inside a specific data directory
+pub fn resolve_in(day: u8, name: &str, data_dir: &Path) -> io::Result<Source> {
+    let path = data_dir.join(format!("{day}.{name}.txt"));
+    if path.is_file() {
+        Ok(Source::File(path))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no input '{name}' for day {day}: {} does not exist",
+                path.display()
+            ),
+        ))
+    }
+}
+
+/// `source` to read as it comes, for days that don't need every line at once
+pub fn open_source(source: &Source) -> io::Result<Box<dyn BufRead>> {
+    match source {
+        Source::Stdin => Ok(Box::new(io::stdin().lock())),
+        Source::File(path) => {
+            let f = File::open(path).map_err(|e| {
+                io::Error::new(e.kind(), format!("could not open {}: {e}", path.display()))
+            })?;
+            Ok(Box::new(BufReader::new(f)))
+        }
+    }
+}
+
+pub fn read_source(source: &Source) -> io::Result<Vec<String>> {
+    open_source(source)?.lines().collect()
+}
+
+pub fn read_input(day: u8, input: Option<&str>) -> io::Result<Vec<String>> {
+    read_source(&resolve(day, input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_workspace(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::create_dir_all(root.join("days/day05/src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"days/*\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("days/day05/Cargo.toml"),
+            "[package]\nname = \"day05\"\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_finds_workspace_from_nested_crate() {
+        let root = scratch_workspace("nested");
+        let found = find_workspace_root(&root.join("days/day05/src"));
+        assert_eq!(found, Some(root));
+    }
+
+    #[test]
+    fn test_named_inputs() {
+        let root = scratch_workspace("named");
+        let data = root.join("data");
+        fs::write(data.join("5.1.txt"), "seeds: 1 2\n").unwrap();
+        fs::write(data.join("5.friend.txt"), "seeds: 3 4\n").unwrap();
+
+        assert_eq!(
+            resolve_in(5, "friend", &data).unwrap(),
+            Source::File(data.join("5.friend.txt"))
+        );
+        let missing = resolve_in(5, "2", &data).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        assert!(missing.to_string().contains("5.2.txt"), "{missing}");
+    }
+
+    #[test]
+    fn test_stdin_and_paths() {
+        let root = scratch_workspace("paths");
+        let path = root.join("data/5.1.txt");
+        fs::write(&path, "seeds: 1 2\n").unwrap();
+
+        assert_eq!(resolve(5, Some("-")).unwrap(), Source::Stdin);
+        let given = path.to_str().unwrap();
+        assert_eq!(resolve(5, Some(given)).unwrap(), Source::File(path.clone()));
+        assert_eq!(
+            read_source(&Source::File(path)).unwrap(),
+            vec!["seeds: 1 2"]
+        );
+    }
+}
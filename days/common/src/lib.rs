@@ -0,0 +1,88 @@
+use std::{fmt::Display, process::ExitCode};
+
+pub mod cli;
+pub mod diagnostic;
+pub mod input;
+
+pub use diagnostic::{annotate, render, Diagnostic, ParseError};
+pub use input::read_input;
+
+/// what a part answers before it's solved. `aoc new` starts days off with
+/// it so the runner prints `unsolved` for them instead of stopping
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+/// something extra for `aoc bench` to time on a day's parsed input, and
+/// what to call it in the results
+pub type BenchPhase<Input> = (&'static str, fn(&Input));
+
+/// one day's puzzle. `parse` turns the raw input lines into whatever the day
+/// actually works on, then both parts run against that
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    type Answer: Display;
+    type Error: Diagnostic;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error>;
+    fn part1(input: &Self::Input) -> Self::Answer;
+    fn part2(input: &Self::Input) -> Self::Answer;
+
+    /// anything besides parsing and the parts that `aoc bench` should time.
+    /// each sample runs every one on the parsed input
+    fn bench_phases() -> Vec<BenchPhase<Self::Input>> {
+        vec![]
+    }
+
+    fn solve_part1(lines: &[String]) -> Result<Self::Answer, Self::Error> {
+        Ok(Self::part1(&Self::parse(lines)?))
+    }
+
+    fn solve_part2(lines: &[String]) -> Result<Self::Answer, Self::Error> {
+        Ok(Self::part2(&Self::parse(lines)?))
+    }
+}
+
+/// what every day binary does by default: read `input` (see [`input`] for
+/// what it accepts), parse once, print both parts
+pub fn run<S: Solution>(input: Option<&str>) -> ExitCode {
+    let lines = match read_input(S::DAY, input) {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let parsed = match S::parse(&lines) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", render(&e, &lines));
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("pt1: {}", S::part1(&parsed));
+    println!("pt2: {}", S::part2(&parsed));
+    ExitCode::SUCCESS
+}
+
+/// generates the `main` for days without flags of their own: [`run`] on the
+/// input from [`cli::InputArgs`]
+///
+/// ```ignore
+/// common::main!(day04::Day04);
+/// ```
+#[macro_export]
+macro_rules! main {
+    ($solution:ty) => {
+        pub fn main() -> std::process::ExitCode {
+            let args = <$crate::cli::InputArgs as $crate::cli::Parser>::parse();
+            $crate::run::<$solution>(args.input.as_deref())
+        }
+    };
+}
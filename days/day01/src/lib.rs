@@ -0,0 +1,192 @@
+use std::{convert::Infallible, fmt, sync::LazyLock};
+
+use common::Solution;
+
+pub mod scanner;
+pub mod vocabulary;
+
+pub use scanner::{sum_lines, Scanner};
+pub use vocabulary::Vocabulary;
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+fn digit_patterns() -> impl Iterator<Item = (&'static str, u32)> {
+    DIGITS.into_iter().zip(0..)
+}
+
+/// pt1 only counts digits
+pub fn digits() -> Scanner {
+    Scanner::new(digit_patterns())
+}
+
+/// pt2 counts the english words for them too, see `Vocabulary` for others
+pub fn digits_and_words() -> Scanner {
+    Vocabulary::english().scanner(true)
+}
+
+static PART1: LazyLock<Scanner> = LazyLock::new(digits);
+static PART2: LazyLock<Scanner> = LazyLock::new(digits_and_words);
+
+/// the calibration value, or `None` for a line without any digits
+fn extract_digits(s: &str) -> Option<i64> {
+    PART1.calibration_value(s.as_bytes()).map(i64::from)
+}
+
+fn part2_extract(s: &str) -> Option<i64> {
+    PART2.calibration_value(s.as_bytes()).map(i64::from)
+}
+
+/// how many skipped lines `Total` lists before it just counts them
+const SHOWN: usize = 10;
+
+/// a part's answer, and the lines it had to leave out for not having digits
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Total {
+    pub sum: i64,
+    /// counting from 1
+    pub skipped: Vec<usize>,
+}
+
+impl Total {
+    /// `line`'s calibration value, if it has one
+    pub fn add(&mut self, line: usize, value: Option<i64>) {
+        match value {
+            Some(value) => self.sum += value,
+            None => self.skipped.push(line),
+        }
+    }
+
+    fn of(lines: &[String], extract: fn(&str) -> Option<i64>) -> Self {
+        let mut total = Total::default();
+        for (i, line) in lines.iter().enumerate() {
+            total.add(i + 1, extract(line));
+        }
+        total
+    }
+}
+
+/// the sum, then any skipped lines, so an input with a bad line never
+/// passes for a right answer
+impl fmt::Display for Total {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sum)?;
+        let Some(more) = self.skipped.len().checked_sub(1) else {
+            return Ok(());
+        };
+        let shown: Vec<String> = self
+            .skipped
+            .iter()
+            .take(SHOWN)
+            .map(ToString::to_string)
+            .collect();
+        let plural = if more > 0 { "s" } else { "" };
+        write!(f, " (skipped line{plural} {}", shown.join(", "))?;
+        if self.skipped.len() > SHOWN {
+            write!(f, " and {} more", self.skipped.len() - SHOWN)?;
+        }
+        write!(f, ")")
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<String>;
+    type Answer = Total;
+    type Error = Infallible;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(lines: &Self::Input) -> Total {
+        Total::of(lines, extract_digits)
+    }
+
+    fn part2(lines: &Self::Input) -> Total {
+        Total::of(lines, part2_extract)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn part1_example<'a>() -> Vec<(&'a str, i64)> {
+        vec![
+            ("1abc2", 12),
+            ("pqr3stu8vwx", 38),
+            ("a1b2c3d4e5f", 15),
+            ("treb7uchet", 77),
+        ]
+    }
+
+    fn part2_example<'a>() -> Vec<(&'a str, i64)> {
+        vec![
+            ("two1nine", 29),
+            ("eightwothree", 83),
+            ("abcone2threexyz", 13),
+            ("xtwone3four", 24),
+            ("4nineeightseven2", 42),
+            ("zoneight234", 14),
+            ("7pqrstsixteen", 76),
+        ]
+    }
+
+    #[test]
+    fn test_basic_extract() {
+        let example = part1_example();
+        for (test, expected) in example {
+            let result = extract_digits(test);
+            assert_eq!(result, Some(expected));
+        }
+    }
+
+    #[test]
+    fn basic_pt2_extract() {
+        let example = part2_example();
+        for (test, expected) in example {
+            let result = part2_extract(test);
+            assert_eq!(result, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_digitless_lines_are_skipped() {
+        assert_eq!(extract_digits("eightwothree"), None);
+        assert_eq!(part2_extract(""), None);
+
+        let lines: Vec<String> = ["1abc2", "abc", "two", "7"].map(String::from).to_vec();
+        let pt1 = Day01::part1(&lines);
+        assert_eq!(pt1.sum, 12 + 77);
+        assert_eq!(pt1.skipped, [2, 3]);
+        assert_eq!(pt1.to_string(), "89 (skipped lines 2, 3)");
+        let pt2 = Day01::part2(&lines);
+        assert_eq!(pt2.to_string(), "111 (skipped line 2)");
+
+        let lines = vec![String::new(); 12];
+        assert_eq!(
+            Day01::part1(&lines).to_string(),
+            "0 (skipped lines 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 and 2 more)"
+        );
+    }
+
+    #[test]
+    fn test_word_extract() {
+        let words = Vocabulary::english().scanner(false);
+        assert_eq!(
+            words.matches(b"two1nine").collect::<Vec<_>>(),
+            vec![(0, 2), (4, 9)]
+        )
+    }
+
+    #[test]
+    fn test_pos_digit_extract() {
+        assert_eq!(
+            digits().matches(b"two1nine").collect::<Vec<_>>(),
+            vec![(3, 1)]
+        )
+    }
+}
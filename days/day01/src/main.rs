@@ -1,148 +1,75 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+//! `day01 [input]` prints both parts like every other day, but reads the
+//! input a line at a time instead of all at once.
+//!
+//! `day01 [input] --vocabulary <name or file> [--reversed] [--ignore-case]`
+//! reads pt2's words from somewhere else: one of english, english-zero,
+//! german or french, or a file of `<word> <value>` lines.
+//!
+//! lines without any digits are left out of the totals, which then say which
+//! lines those were. `--strict` stops at the first one instead.
 
-fn extract_digits(s: &String) -> i32 {
-    let digits: Vec<i32> = s
-        .chars()
-        .filter(|c| c.is_digit(10))
-        .map(|c| c.to_string().parse().unwrap())
-        .collect::<Vec<_>>();
-    let first = digits[0];
-    let last = digits[digits.len() - 1];
-    (first * 10) + last
-}
-
-pub fn main() -> std::io::Result<()> {
-    let f = File::open("data/1.1.txt")?;
-    let reader: BufReader<File> = BufReader::new(f);
-    let lines = reader.lines().flatten().collect::<Vec<_>>();
-    println!("pt1: {}", pt1(&lines));
-    println!("pt2: {}", pt2(&lines));
-    Ok(())
-}
-
-const NUMBERS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
-fn number_word_positions(s: &String) -> Vec<(usize, usize)> {
-    let mut o = vec![];
-    for (i, word) in NUMBERS.iter().enumerate() {
-        // slide
-        if s.len() < word.len() {
-            break;
-        }
-        for position in 0..1 + s.len() - word.len() {
-            if position + word.len() > s.len() {
-                break;
-            }
-            let substr = &s[position..(position + word.len())];
-            if substr == *word {
-                o.push((position, i + 1));
-            }
-        }
-    }
-    o
-}
+use std::{path::Path, process::ExitCode};
 
-fn number_digit_positions(s: &String) -> Vec<(usize, usize)> {
-    s.chars()
-        .enumerate()
-        .map(|(i, c)| {
-            if c.is_digit(10) {
-                Some((i, c.to_string().parse().unwrap()))
-            } else {
-                None
-            }
-        })
-        .flatten()
-        .collect::<Vec<_>>()
-}
+use clap::Parser;
+use common::{cli::InputArgs, input, Solution};
+use day01::{Day01, Total, Vocabulary};
 
-fn part2_extract(s: &String) -> i32 {
-    let by_digit = number_digit_positions(s);
-    let by_word = number_word_positions(s);
-    let mut positions = by_digit.iter().chain(by_word.iter()).collect::<Vec<_>>();
-    positions.sort();
-    let first = positions[0].1 as i32;
-    let last = positions[positions.len() - 1].1 as i32;
-    (first * 10) + last
+/// day 1's calibration values, read a line at a time
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+    /// pt2's words: english, english-zero, german, french, or a file of
+    /// `<word> <value>` lines
+    #[arg(long, value_name = "NAME_OR_FILE")]
+    vocabulary: Option<String>,
+    /// spell every word backwards
+    #[arg(long)]
+    reversed: bool,
+    /// match words whatever their case
+    #[arg(long)]
+    ignore_case: bool,
+    /// stop at the first line without any digits instead of skipping it
+    #[arg(long)]
+    strict: bool,
 }
 
-pub fn pt1(lines: &Vec<String>) -> i32 {
-    let mut total = 0;
-    for line in lines {
-        total += extract_digits(&line);
+/// the words from `--vocabulary`, by name if it's one and from a file if not
+fn vocabulary(args: &Args) -> Result<Vocabulary, String> {
+    let mut vocabulary = match args.vocabulary.as_deref() {
+        None => Vocabulary::english(),
+        Some(name) => match Vocabulary::named(name) {
+            Some(vocabulary) => vocabulary,
+            None => Vocabulary::load(Path::new(name))?,
+        },
+    };
+    if args.reversed {
+        vocabulary = vocabulary.reversed();
     }
-    total
-}
-
-pub fn pt2(lines: &Vec<String>) -> i32 {
-    let mut total = 0;
-    for line in lines {
-        total += part2_extract(&line);
+    if args.ignore_case {
+        vocabulary = vocabulary.ignoring_case();
     }
-    total
+    Ok(vocabulary)
 }
 
-#[cfg(test)]
-mod tests {
-
-    use super::*;
-
-    fn part1_example<'a>() -> Vec<(&'a str, i32)> {
-        vec![
-            ("1abc2", 12),
-            ("pqr3stu8vwx", 38),
-            ("a1b2c3d4e5f", 15),
-            ("treb7uchet", 77),
-        ]
-    }
-
-    fn part2_example<'a>() -> Vec<(&'a str, i32)> {
-        vec![
-            ("two1nine", 29),
-            ("eightwothree", 83),
-            ("abcone2threexyz", 13),
-            ("xtwone3four", 24),
-            ("4nineeightseven2", 42),
-            ("zoneight234", 14),
-            ("7pqrstsixteen", 76),
-        ]
-    }
+fn run(args: &Args) -> Result<[Total; 2], String> {
+    let words = vocabulary(args)?.scanner(true);
+    input::resolve(Day01::DAY, args.input.input.as_deref())
+        .and_then(|source| input::open_source(&source))
+        .and_then(|reader| day01::sum_lines(reader, [&day01::digits(), &words], args.strict))
+        .map_err(|e| e.to_string())
+}
 
-    #[test]
-    fn test_basic_extract() {
-        let example = part1_example();
-        for (test, expected) in example {
-            let result = extract_digits(&test.to_string());
-            assert_eq!(result, expected);
+fn main() -> ExitCode {
+    match run(&Args::parse()) {
+        Ok([pt1, pt2]) => {
+            println!("pt1: {pt1}");
+            println!("pt2: {pt2}");
+            ExitCode::SUCCESS
         }
-    }
-
-    #[test]
-    fn basic_pt2_extract() {
-        let example = part2_example();
-        for (test, expected) in example {
-            let result = part2_extract(&test.to_string());
-            assert_eq!(result, expected);
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
         }
     }
-
-    #[test]
-    fn test_word_extract() {
-        assert_eq!(
-            number_word_positions(&"two1nine".to_string()),
-            vec![(0, 2), (4, 9)]
-        )
-    }
-
-    #[test]
-    fn test_pos_digit_extract() {
-        assert_eq!(
-            number_digit_positions(&"two1nine".to_string()),
-            vec![(3, 1)]
-        )
-    }
 }
@@ -0,0 +1,219 @@
+//! finding digits, spelled out or not, in one pass over a line.
+//!
+//! the patterns go into an aho-corasick automaton: a trie of them where every
+//! state also knows where to go on a byte that doesn't carry on its pattern.
+//! reading a line is one table lookup per byte, and matches that overlap,
+//! like "eightwo" or "oneight", are all seen.
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+use crate::Total;
+
+const ROOT: usize = 0;
+/// no edge, while the trie is being built
+const NONE: u32 = u32::MAX;
+
+pub struct Scanner {
+    /// what each byte reads as, to fold case or not
+    fold: [u8; 256],
+    /// `next[state][byte]` is the state after reading `byte`
+    next: Vec<[u32; 256]>,
+    /// the patterns ending at each state, as `(length, value)`. that includes
+    /// ones ending at a suffix of the state, like "two" inside "eightwo"
+    ends: Vec<Vec<(usize, u32)>>,
+}
+
+impl Scanner {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self::build(patterns, std::array::from_fn(|b| b as u8))
+    }
+
+    /// like `new`, but ascii letters match whatever their case
+    pub fn ignoring_case<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self::build(
+            patterns,
+            std::array::from_fn(|b| (b as u8).to_ascii_lowercase()),
+        )
+    }
+
+    fn build<'a>(patterns: impl IntoIterator<Item = (&'a str, u32)>, fold: [u8; 256]) -> Self {
+        let mut next = vec![[NONE; 256]];
+        let mut ends = vec![vec![]];
+        for (pattern, value) in patterns {
+            assert!(!pattern.is_empty(), "an empty pattern matches everywhere");
+            let mut state = ROOT;
+            for b in pattern.bytes().map(|b| fold[b as usize]) {
+                if next[state][b as usize] == NONE {
+                    next[state][b as usize] = next.len() as u32;
+                    next.push([NONE; 256]);
+                    ends.push(vec![]);
+                }
+                state = next[state][b as usize] as usize;
+            }
+            ends[state].push((pattern.len(), value));
+        }
+
+        // breadth first, so a state's failure state (the longest suffix of
+        // its text that's also in the trie) is always finished before it
+        let mut fail = vec![ROOT; next.len()];
+        let mut queue = VecDeque::new();
+        for edge in &mut next[ROOT] {
+            match *edge {
+                NONE => *edge = ROOT as u32,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let inherited = ends[fail[state]].clone();
+            ends[state].extend(inherited);
+            let fallback = next[fail[state]];
+            for (edge, via_fail) in next[state].iter_mut().zip(fallback) {
+                match *edge {
+                    NONE => *edge = via_fail,
+                    child => {
+                        fail[child as usize] = via_fail as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Self { fold, next, ends }
+    }
+
+    /// every match in `line` as `(start, value)`, in the order they end
+    pub fn matches<'s>(&'s self, line: &'s [u8]) -> impl Iterator<Item = (usize, u32)> + 's {
+        line.iter()
+            .scan(ROOT, |state, &b| {
+                let b = self.fold[b as usize];
+                *state = self.next[*state][b as usize] as usize;
+                Some(*state)
+            })
+            .enumerate()
+            .flat_map(|(i, state)| {
+                self.ends[state]
+                    .iter()
+                    .map(move |&(len, value)| (i + 1 - len, value))
+            })
+    }
+
+    /// the values of the first and last matches in `line`, by where they start
+    pub fn first_and_last(&self, line: &[u8]) -> Option<(u32, u32)> {
+        let mut first: Option<(usize, u32)> = None;
+        let mut last: Option<(usize, u32)> = None;
+        for (start, value) in self.matches(line) {
+            if first.is_none_or(|(at, _)| start < at) {
+                first = Some((start, value));
+            }
+            if last.is_none_or(|(at, _)| start >= at) {
+                last = Some((start, value));
+            }
+        }
+        Some((first?.1, last?.1))
+    }
+
+    /// the first and last digits as a two digit number
+    pub fn calibration_value(&self, line: &[u8]) -> Option<u32> {
+        self.first_and_last(line)
+            .map(|(first, last)| first * 10 + last)
+    }
+}
+
+/// the calibration values of every line in `reader` added up, once for each
+/// of `scanners`, reading a line at a time. lines without digits are skipped,
+/// or with `strict` stop it with an error
+pub fn sum_lines<const N: usize>(
+    mut reader: impl BufRead,
+    scanners: [&Scanner; N],
+    strict: bool,
+) -> io::Result<[Total; N]> {
+    let mut totals = std::array::from_fn(|_| Total::default());
+    let mut line = vec![];
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(totals);
+        }
+        number += 1;
+        for (total, scanner) in totals.iter_mut().zip(scanners) {
+            let value = scanner.calibration_value(&line);
+            if strict && value.is_none() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {number} has no digits"),
+                ));
+            }
+            total.add(number, value.map(i64::from));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words() -> Scanner {
+        crate::Vocabulary::english().scanner(false)
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let words = words();
+        for (line, expected) in [
+            ("eightwo", (8, 2)),
+            ("oneight", (1, 8)),
+            ("twone", (2, 1)),
+            ("sevenine", (7, 9)),
+            ("xeightwothreex", (8, 3)),
+            ("nine", (9, 9)),
+        ] {
+            assert_eq!(
+                words.first_and_last(line.as_bytes()),
+                Some(expected),
+                "{line}"
+            );
+        }
+        assert_eq!(words.first_and_last(b"seve nin"), None);
+        assert_eq!(
+            words.matches(b"oneightwo").collect::<Vec<_>>(),
+            [(0, 1), (2, 8), (6, 2)]
+        );
+    }
+
+    #[test]
+    fn test_patterns_inside_patterns() {
+        // the textbook example, where "she" has "he" inside it and "hers"
+        // starts with "he"
+        let scanner = Scanner::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+        assert_eq!(
+            scanner.matches(b"ushers").collect::<Vec<_>>(),
+            [(1, 2), (2, 1), (2, 4)]
+        );
+        // first by start, last by start and then the longest
+        assert_eq!(scanner.first_and_last(b"ushers"), Some((2, 4)));
+    }
+
+    #[test]
+    fn test_sum_lines_streams() {
+        let input = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n\
+                     4nineeightseven2\nzoneight234\n7pqrstsixteen";
+        let (digits, both) = (crate::digits(), crate::digits_and_words());
+        let [total] = sum_lines(input.as_bytes(), [&both], true).unwrap();
+        assert_eq!(total.sum, 281);
+
+        let e = sum_lines(input.as_bytes(), [&digits, &both], true).unwrap_err();
+        assert_eq!(e.to_string(), "line 2 has no digits");
+        let [pt1, pt2] = sum_lines(input.as_bytes(), [&digits, &both], false).unwrap();
+        assert_eq!(
+            (pt1.sum, pt1.skipped),
+            (11 + 22 + 33 + 42 + 24 + 77, vec![2])
+        );
+        assert_eq!((pt2.sum, pt2.skipped), (281, vec![]));
+
+        let [total] = sum_lines("1abc2\r\npqr3stu8vwx\n".as_bytes(), [&digits], true).unwrap();
+        assert_eq!(total.sum, 12 + 38);
+    }
+}
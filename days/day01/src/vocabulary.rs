@@ -0,0 +1,253 @@
+//! the words pt2 reads as numbers. the puzzle only wants english "one" to
+//! "nine", but the scanner takes any words, so other languages, spellings
+//! and files of words work the same way.
+
+use std::{fmt, fs, path::Path};
+
+use common::ParseError;
+
+use crate::{digit_patterns, Scanner};
+
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const FRENCH: [&str; 10] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+    ignore_case: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VocabularyErrorKind {
+    Entry,
+    Value,
+    /// a value past 9, which wouldn't be a digit of the calibration value
+    Digit,
+}
+
+impl fmt::Display for VocabularyErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyErrorKind::Entry => write!(f, "`<word> <value>`"),
+            VocabularyErrorKind::Value => write!(f, "a number"),
+            VocabularyErrorKind::Digit => write!(f, "a value from 0 to 9"),
+        }
+    }
+}
+
+pub type VocabularyError = ParseError<VocabularyErrorKind>;
+
+impl Vocabulary {
+    /// what `named` knows
+    pub const NAMES: [&'static str; 4] = ["english", "english-zero", "german", "french"];
+
+    fn numbered(words: &[&str], from: u32) -> Self {
+        Self {
+            words: words.iter().map(|w| w.to_string()).zip(from..).collect(),
+            ignore_case: false,
+        }
+    }
+
+    /// the puzzle's, "one" to "nine"
+    pub fn english() -> Self {
+        Self::numbered(&ENGLISH[1..], 1)
+    }
+
+    pub fn english_with_zero() -> Self {
+        Self::numbered(&ENGLISH, 0)
+    }
+
+    pub fn german() -> Self {
+        Self::numbered(&GERMAN, 0)
+    }
+
+    pub fn french() -> Self {
+        Self::numbered(&FRENCH, 0)
+    }
+
+    /// one of `NAMES`
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Self::english()),
+            "english-zero" => Some(Self::english_with_zero()),
+            "german" => Some(Self::german()),
+            "french" => Some(Self::french()),
+            _ => None,
+        }
+    }
+
+    /// every word spelled backwards, "eno" for "one"
+    pub fn reversed(mut self) -> Self {
+        for (word, _) in &mut self.words {
+            *word = word.chars().rev().collect();
+        }
+        self
+    }
+
+    /// match words whatever their case. only ascii letters fold, so "FÜNF"
+    /// still isn't "fünf"
+    pub fn ignoring_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    /// a scanner for these words, and for the digits themselves with `digits`
+    pub fn scanner(&self, digits: bool) -> Scanner {
+        let mut patterns: Vec<(&str, u32)> = vec![];
+        if digits {
+            for digit in digit_patterns() {
+                patterns.push(digit);
+            }
+        }
+        patterns.extend(self.words());
+        if self.ignore_case {
+            Scanner::ignoring_case(patterns)
+        } else {
+            Scanner::new(patterns)
+        }
+    }
+
+    /// one `<word> <value>` a line, values from 0 to 9. blank lines and ones
+    /// starting with `#` are skipped, and words can't have spaces in them
+    pub fn parse(text: &str) -> Result<Self, VocabularyError> {
+        let mut words = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let [(_, word), (column, value)] = tokens(line)[..] else {
+                return Err(
+                    VocabularyError::new(VocabularyErrorKind::Entry, 0..line.len()).offset(i, 0),
+                );
+            };
+            let error = |kind| VocabularyError::new(kind, 0..value.len()).offset(i, column);
+            let value: u32 = value
+                .parse()
+                .map_err(|_| error(VocabularyErrorKind::Value))?;
+            if value > 9 {
+                return Err(error(VocabularyErrorKind::Digit));
+            }
+            words.push((word.to_string(), value));
+        }
+        Ok(Self {
+            words,
+            ignore_case: false,
+        })
+    }
+
+    /// `parse` a file, with any error rendered against it
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| {
+            let lines: Vec<String> = text.lines().map(String::from).collect();
+            format!(
+                "{} isn't a vocabulary:\n{}",
+                path.display(),
+                common::render(&e, &lines)
+            )
+        })
+    }
+}
+
+/// the whitespace separated words of `line`, each with the byte it starts at
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                tokens.push((s, &line[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(vocabulary: &Vocabulary, line: &str) -> Option<u32> {
+        vocabulary.scanner(true).calibration_value(line.as_bytes())
+    }
+
+    #[test]
+    fn test_built_in() {
+        assert_eq!(value(&Vocabulary::english(), "zero7one"), Some(71));
+        assert_eq!(value(&Vocabulary::english_with_zero(), "zero7one"), Some(1));
+        assert_eq!(value(&Vocabulary::german(), "xsiebenullfünfzig"), Some(75));
+        assert_eq!(value(&Vocabulary::french(), "zérotroisept"), Some(7));
+        assert_eq!(value(&Vocabulary::french(), "xquatreuneufx"), Some(49));
+        for name in Vocabulary::NAMES {
+            assert!(Vocabulary::named(name).is_some(), "{name}");
+        }
+        assert_eq!(Vocabulary::named("klingon"), None);
+    }
+
+    #[test]
+    fn test_reversed_and_case() {
+        let reversed = Vocabulary::english().reversed();
+        assert_eq!(value(&reversed, "owtxeno"), Some(21));
+        assert_eq!(value(&reversed, "eightwo"), None);
+        assert_eq!(value(&Vocabulary::german().reversed(), "fnüf"), Some(55));
+
+        let any_case = Vocabulary::english().ignoring_case();
+        assert_eq!(value(&any_case, "EightwO"), Some(82));
+        assert_eq!(value(&Vocabulary::english(), "EightwO"), None);
+        let german = Vocabulary::german().ignoring_case();
+        assert_eq!(value(&german, "ACHTfünf"), Some(85));
+        assert_eq!(value(&german, "FÜNF"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "# roman numerals\n\ni 1\nv 5\n  ix   9\niv 4\n";
+        let roman = Vocabulary::parse(text).unwrap();
+        assert_eq!(roman.words().count(), 4);
+        // "iv" overlaps "i" and "v", and the last to start is "v"
+        let scanner = roman.scanner(false);
+        assert_eq!(scanner.first_and_last(b"ixiv"), Some((1, 5)));
+        assert_eq!(
+            scanner.matches(b"ixiv").collect::<Vec<_>>(),
+            [(0, 1), (0, 9), (2, 1), (2, 4), (3, 5)]
+        );
+
+        let e = Vocabulary::parse("one 1\ntwo\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.columns, e.kind),
+            (1, 0..3, VocabularyErrorKind::Entry)
+        );
+        let e = Vocabulary::parse("one 1\n  two  2x").unwrap_err();
+        assert_eq!(
+            (e.line, e.columns, e.kind),
+            (1, 7..9, VocabularyErrorKind::Value)
+        );
+        // columns are bytes, so they still line up after a wider character
+        let e = Vocabulary::parse("fünf\t5\nzehn 10").unwrap_err();
+        assert_eq!(
+            (e.line, e.columns, e.kind),
+            (1, 5..7, VocabularyErrorKind::Digit)
+        );
+        let e = Vocabulary::parse("zwölf 12").unwrap_err();
+        assert_eq!(e.columns, 7..9);
+        assert!(Vocabulary::parse("fünf\t5\n").is_ok());
+    }
+}
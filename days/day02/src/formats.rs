@@ -0,0 +1,222 @@
+//! game logs in other shapes than the puzzle's: json, as the games are, or
+//! csv with one row per color per round, which spreadsheets and the like can
+//! load straight in. either way they have to say the same as a text log
+//! could, so every game has rounds and every round has cubes.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use common::Solution;
+use serde::{Deserialize, Serialize};
+
+use crate::{is_color, Day02, Game, Round};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// the puzzle's `Game 1: 3 blue, 4 red; ...` lines
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("'{s}' is not a format, expected text, json or csv")),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// one color drawn in one round. `index` is where the game is in the log,
+/// since ids can repeat. both it and rounds count from 1
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Row {
+    index: usize,
+    game: u32,
+    round: usize,
+    color: String,
+    count: u32,
+}
+
+/// `games` as `format`, ending in a newline
+pub fn write(games: &[Game], format: Format) -> Result<String, String> {
+    match format {
+        Format::Text => Ok(games.iter().map(|g| format!("{g}\n")).collect()),
+        Format::Json => serde_json::to_string_pretty(games)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            for (index, game) in games.iter().enumerate() {
+                for (i, round) in game.rounds.iter().enumerate() {
+                    for (color, count) in round {
+                        let row = Row {
+                            index: index + 1,
+                            game: game.id,
+                            round: i + 1,
+                            color: color.clone(),
+                            count: *count,
+                        };
+                        writer.serialize(row).map_err(|e| e.to_string())?;
+                    }
+                }
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// games written by `write`, or by hand. text errors come back rendered.
+///
+/// csv rows go in order: a row starts a new game when its index goes up,
+/// and a new round when its round does
+pub fn read(text: &str, format: Format) -> Result<Vec<Game>, String> {
+    let games = match format {
+        Format::Text => {
+            let lines: Vec<String> = text.lines().map(String::from).collect();
+            return Day02::parse(&lines).map_err(|e| common::render(&e, &lines));
+        }
+        Format::Json => serde_json::from_str(text).map_err(|e| e.to_string())?,
+        Format::Csv => read_csv(text)?,
+    };
+    for (index, game) in games.iter().enumerate() {
+        check(index + 1, game)?;
+    }
+    Ok(games)
+}
+
+fn read_csv(text: &str) -> Result<Vec<Game>, String> {
+    let mut games: Vec<Game> = vec![];
+    let mut last: Option<(usize, usize)> = None;
+    for (i, row) in csv::Reader::from_reader(text.as_bytes())
+        .deserialize()
+        .enumerate()
+    {
+        let row: Row = row.map_err(|e| e.to_string())?;
+        let out_of_order = || format!("row {} goes back to an earlier game or round", i + 1);
+        match last {
+            Some((index, round)) if index == row.index => match round.cmp(&row.round) {
+                Ordering::Less => games.last_mut().unwrap().rounds.push(Round::new()),
+                Ordering::Equal => {}
+                Ordering::Greater => return Err(out_of_order()),
+            },
+            Some((index, _)) if index > row.index => return Err(out_of_order()),
+            _ => games.push(Game {
+                id: row.game,
+                rounds: vec![Round::new()],
+            }),
+        }
+        let round = games.last_mut().unwrap().rounds.last_mut().unwrap();
+        round.push((row.color, row.count));
+        last = Some((row.index, row.round));
+    }
+    Ok(games)
+}
+
+/// `game`, the `index`th in its log, could have been read from text
+fn check(index: usize, game: &Game) -> Result<(), String> {
+    let which = format!("game {index} in the log (id {})", game.id);
+    if game.rounds.is_empty() {
+        return Err(format!("{which} has no rounds"));
+    }
+    if game.rounds.iter().any(Vec::is_empty) {
+        return Err(format!("{which} has a round without any cubes"));
+    }
+    match game
+        .rounds
+        .iter()
+        .flatten()
+        .find(|(color, _)| !is_color(color))
+    {
+        Some((color, _)) => Err(format!(
+            "{which} has '{color}' for a color, expected one word"
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+                       Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n";
+
+    #[test]
+    fn test_formats_round_trip() {
+        let games = read(LOG, Format::Text).unwrap();
+        for format in [Format::Text, Format::Json, Format::Csv] {
+            let written = write(&games, format).unwrap();
+            assert_eq!(read(&written, format).unwrap(), games, "{format}");
+        }
+        assert_eq!(write(&games, Format::Text).unwrap(), LOG);
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        // the same id twice in a row, both starting at round 1
+        let log = "Game 7: 2 red; 1 blue, 3 red\nGame 7: 5 red\n";
+        let games = read(log, Format::Text).unwrap();
+        let csv = write(&games, Format::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "index,game,round,color,count\n\
+             1,7,1,red,2\n\
+             1,7,2,blue,1\n\
+             1,7,2,red,3\n\
+             2,7,1,red,5\n"
+        );
+        assert_eq!(read(&csv, Format::Csv).unwrap(), games);
+        assert_eq!(write(&games, Format::Text).unwrap(), log);
+
+        assert!(read("index,game,round,color\n1,1,1,red\n", Format::Csv).is_err());
+        assert!(read("index,game,round,color,count\n1,1,1,red,-1\n", Format::Csv).is_err());
+        let e = read(
+            "index,game,round,color,count\n2,1,1,red,1\n1,1,1,red,1\n",
+            Format::Csv,
+        )
+        .unwrap_err();
+        assert_eq!(e, "row 2 goes back to an earlier game or round");
+        let e = read(
+            "index,game,round,color,count\n1,1,1,dark red,1\n",
+            Format::Csv,
+        )
+        .unwrap_err();
+        assert!(e.contains("'dark red'"), "{e}");
+    }
+
+    #[test]
+    fn test_json_needs_rounds_with_cubes() {
+        let e = read(r#"[{"id": 1, "rounds": []}]"#, Format::Json).unwrap_err();
+        assert_eq!(e, "game 1 in the log (id 1) has no rounds");
+        let e = read(
+            r#"[{"id": 1, "rounds": [[["red", 1]]]}, {"id": 4, "rounds": [[]]}]"#,
+            Format::Json,
+        )
+        .unwrap_err();
+        assert_eq!(e, "game 2 in the log (id 4) has a round without any cubes");
+        let games = read(
+            r#"[{"id": 1, "rounds": [[["red", 1], ["red", 2]]]}]"#,
+            Format::Json,
+        );
+        assert_eq!(
+            write(&games.unwrap(), Format::Text).unwrap(),
+            "Game 1: 1 red, 2 red\n"
+        );
+    }
+}
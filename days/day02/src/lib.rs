@@ -0,0 +1,421 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
+
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+
+pub mod formats;
+
+/// the cubes shown in one round, color by color in the order they were
+/// listed. the same color can come up twice
+pub type Round = Vec<(String, u32)>;
+
+/// cubes by color. colors that aren't in it count as 0
+pub type Cubes = BTreeMap<String, u32>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    /// the most of each color shown in any one round, which is also the
+    /// fewest cubes the bag could have held
+    pub fn max(&self) -> Cubes {
+        let mut out = Cubes::new();
+        for round in &self.rounds {
+            for (color, count) in added_up(round) {
+                let most = out.entry(color).or_insert(0);
+                *most = count.max(*most);
+            }
+        }
+        out
+    }
+
+    pub fn can_be_playable_with(&self, bag: &Bag) -> bool {
+        self.max()
+            .iter()
+            .all(|(color, &count)| bag.count(color) >= count)
+    }
+
+    /// the fewest cubes of each of `bag`'s colors multiplied together. a
+    /// color the game never draws needs 0 cubes, which makes it 0 too.
+    /// `None` if that doesn't fit, which a bag of three colors never hits
+    pub fn power(&self, bag: &Bag) -> Option<u128> {
+        let max = self.max();
+        bag.colors()
+            .map(|color| max.get(color).copied().unwrap_or(0))
+            .try_fold(1u128, |power, count| power.checked_mul(count.into()))
+    }
+}
+
+/// the cubes the elf says are in the bag
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bag(Cubes);
+
+impl Bag {
+    pub fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// `<count> <color>`s split by commas or lines, with any error rendered
+    /// against the file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        let mut cubes = Cubes::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let round = parse_cubes(line, 0).map_err(|e| {
+                format!(
+                    "{} isn't a bag:\n{}",
+                    path.display(),
+                    common::render(&e.offset(i, 0), &lines)
+                )
+            })?;
+            for (color, count) in round {
+                *cubes.entry(color).or_insert(0) += count;
+            }
+        }
+        Ok(Self(cubes))
+    }
+}
+
+/// the puzzle's, 12 red, 13 green and 14 blue
+impl Default for Bag {
+    fn default() -> Self {
+        Self(Cubes::from([
+            ("red".to_string(), 12),
+            ("green".to_string(), 13),
+            ("blue".to_string(), 14),
+        ]))
+    }
+}
+
+/// `12 red, 13 green, 14 blue`, like a round
+impl FromStr for Bag {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cubes(s, 0).map(|round| Self(added_up(&round)))
+    }
+}
+
+/// what the game parser was looking for when it gave up
+#[derive(Debug, PartialEq)]
+pub enum GameErrorKind {
+    Header,
+    Id,
+    Count,
+    Color,
+}
+
+impl fmt::Display for GameErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameErrorKind::Header => write!(f, "`Game <id>:`"),
+            GameErrorKind::Id => write!(f, "a game id"),
+            GameErrorKind::Count => write!(f, "a cube count"),
+            GameErrorKind::Color => write!(f, "a color, in one word"),
+        }
+    }
+}
+
+pub type GameError = ParseError<GameErrorKind>;
+
+/// back the way the puzzle writes it, so a game read from a line prints as
+/// that line again
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ";" };
+            write!(f, "{separator}")?;
+            for (j, (color, count)) in round.iter().enumerate() {
+                let separator = if j == 0 { " " } else { ", " };
+                write!(f, "{separator}{count} {color}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = GameError;
+
+    // Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use GameErrorKind::*;
+        let (game, rest) = s
+            .split_once(':')
+            .ok_or(GameError::new(Header, 0..s.len()))?;
+        let id_raw = game
+            .strip_prefix("Game ")
+            .ok_or(GameError::new(Header, 0..game.len()))?;
+        let id = number(id_raw).ok_or(GameError::new(Id, 5..game.len()))?;
+
+        let mut rounds = vec![];
+        // byte position of the current round inside `s`, for error spans
+        let mut round_start = game.len() + 1;
+        for round in rest.split(';') {
+            rounds.push(parse_cubes(round, round_start)?);
+            round_start += round.len() + 1;
+        }
+        Ok(Game { id, rounds })
+    }
+}
+
+/// `8 green, 6 blue, 20 red`, starting `start` bytes into the line
+fn parse_cubes(s: &str, start: usize) -> Result<Round, GameError> {
+    use GameErrorKind::*;
+    let mut counts = Round::new();
+    // byte position of the current color inside the line, for error spans
+    let mut color_start = start;
+    for color in s.split(',') {
+        let trimmed = color.trim_start();
+        let start = color_start + color.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        let (count, name) = trimmed
+            .split_once(' ')
+            .ok_or(GameError::new(Count, start..start + trimmed.len()))?;
+        let count = number(count).ok_or(GameError::new(Count, start..start + count.len()))?;
+        let name_start = start + trimmed.len() - name.len();
+        if !is_color(name) {
+            return Err(GameError::new(Color, name_start..name_start + name.len()));
+        }
+        counts.push((name.to_string(), count));
+        color_start += color.len() + 1;
+    }
+    Ok(counts)
+}
+
+/// just digits, the way the puzzle writes ids and counts. `str::parse` would
+/// also take a `+`, which wouldn't print back
+fn number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// one word of ascii letters
+fn is_color(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// `round`'s cubes by color, with a color shown twice added up
+fn added_up(round: &Round) -> Cubes {
+    let mut cubes = Cubes::new();
+    for (color, count) in round {
+        *cubes.entry(color.clone()).or_insert(0) += count;
+    }
+    cubes
+}
+
+/// the ids of the games `bag` could have played, added up
+pub fn possible_with(games: &[Game], bag: &Bag) -> u32 {
+    games
+        .iter()
+        .filter(|g| g.can_be_playable_with(bag))
+        .map(|g| g.id)
+        .sum()
+}
+
+/// every game's power over `bag`'s colors, added up, or `None` if that
+/// doesn't fit
+pub fn power_with(games: &[Game], bag: &Bag) -> Option<u128> {
+    games
+        .iter()
+        .try_fold(0u128, |sum, g| sum.checked_add(g.power(bag)?))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Game>;
+    // the powers are three counts multiplied, which only u128 always holds
+    type Answer = u128;
+    type Error = GameError;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, g)| g.parse::<Game>().map_err(|e| e.offset(i, 0)))
+            .collect()
+    }
+
+    // 12 red cubes, 13 green cubes, and 14 blue cubes
+    fn part1(games: &Self::Input) -> u128 {
+        possible_with(games, &Bag::default()).into()
+    }
+
+    // find the min number of cubes that could be in a bag to make the game possible
+    // sum up the products of every game
+    fn part2(games: &Self::Input) -> u128 {
+        // each power is under 2^96, so only 2^32 games of them could overflow
+        power_with(games, &Bag::default()).expect("the puzzle's bag has three colors")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn round(counts: &[(&str, u32)]) -> Round {
+        counts.iter().map(|&(c, n)| (c.to_string(), n)).collect()
+    }
+
+    fn cubes(counts: &[(&str, u32)]) -> Cubes {
+        counts.iter().map(|&(c, n)| (c.to_string(), n)).collect()
+    }
+
+    #[test]
+    fn test_game_parse() {
+        let raw = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+        let p: Game = raw.parse().unwrap();
+        assert_eq!(
+            Game {
+                id: 3,
+                rounds: vec![
+                    round(&[("green", 8), ("blue", 6), ("red", 20)]),
+                    round(&[("blue", 5), ("red", 4), ("green", 13)]),
+                    round(&[("green", 5), ("red", 1)]),
+                ],
+            },
+            p
+        )
+    }
+
+    #[test]
+    fn test_example() {
+        let lines: Vec<String> = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let answer = Day02::solve_part1(&lines).unwrap();
+        assert_eq!(8, answer);
+        assert_eq!(Day02::solve_part2(&lines).unwrap(), 2286);
+
+        // a game without blue needs no blue cubes
+        let lines = vec!["Game 1: 3 red, 2 green; 1 red".to_string()];
+        assert_eq!(Day02::solve_part2(&lines).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_game_parse_errors() {
+        let e = "Game 3: 8 green, 6 sky blue".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Color, 19..27));
+
+        let e = "Game 3: 8 green; x blue".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Count, 17..18));
+
+        let e = "Game three: 8 green".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Id, 5..10));
+
+        // no signs, they wouldn't print back
+        let e = "Game 3: -3 red".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Count, 8..10));
+        let e = "Game 3: +5 red".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Count, 8..10));
+        let e = "Game -3: 5 red".parse::<Game>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Id, 5..7));
+
+        let lines: Vec<String> = vec!["Game 1: 1 red".into(), "Game 2 1 red".into()];
+        let e = Day02::parse(&lines).unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Header, 0..12).offset(1, 0));
+    }
+
+    #[test]
+    fn test_any_colors() {
+        let game: Game = "Game 7: 2 purple, 1 red; 5 purple, 1 red, 1 red"
+            .parse()
+            .unwrap();
+        assert_eq!(game.max(), cubes(&[("purple", 5), ("red", 2)]));
+        // no green or blue, so none of those needed
+        assert_eq!(game.power(&Bag::default()), Some(0));
+        assert_eq!(game.power(&"5 purple, 2 red".parse().unwrap()), Some(10));
+        assert!(!game.can_be_playable_with(&Bag::default()));
+        assert!(game.can_be_playable_with(&"5 purple, 2 red".parse().unwrap()));
+        assert!(!game.can_be_playable_with(&"5 purple, 1 red, 9 blue".parse().unwrap()));
+
+        let e = "4 red, 3 dark green".parse::<Bag>().unwrap_err();
+        assert_eq!(e, GameError::new(GameErrorKind::Color, 9..19));
+    }
+
+    #[test]
+    fn test_bag_load() {
+        let dir = std::env::temp_dir().join(format!("day02-bag-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bag.txt");
+        fs::write(&path, "12 red, 13 green\n\n14 blue\n1 red\n").unwrap();
+        let bag = Bag::load(&path).unwrap();
+        assert_eq!(bag, "13 red, 13 green, 14 blue".parse().unwrap());
+
+        fs::write(&path, "12 red\n13 gr33n\n").unwrap();
+        let e = Bag::load(&path).unwrap_err();
+        assert!(e.contains("isn't a bag") && e.contains("line 2"), "{e}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_power_overflow() {
+        let max = u32::MAX;
+        let game: Game = format!("Game 1: {max} red, {max} green, {max} blue; 1 purple, 1 pink")
+            .parse()
+            .unwrap();
+        let power = u128::from(max).pow(3);
+        assert_eq!(game.power(&Bag::default()), Some(power));
+        let games = vec![game.clone(), game.clone()];
+        assert_eq!(Day02::part2(&games), 2 * power);
+
+        // five colors of u32::MAX don't fit
+        let bag = "1 red, 1 green, 1 blue, 1 purple, 1 pink".parse().unwrap();
+        let game: Game =
+            format!("Game 1: {max} red, {max} green, {max} blue, {max} purple, {max} pink")
+                .parse()
+                .unwrap();
+        assert_eq!(game.power(&bag), None);
+        assert_eq!(power_with(&[game], &bag), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let raw = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 1 red, 13 green; 5 green";
+        let game: Game = raw.parse().unwrap();
+        assert_eq!(game.to_string(), raw);
+    }
+
+    /// a game line the way the puzzle writes them, colors in any order and
+    /// sometimes more than once
+    fn line() -> impl Strategy<Value = String> {
+        let cubes = (any::<u32>(), "[a-zA-Z]{1,8}").prop_map(|(n, c)| format!("{n} {c}"));
+        let round = prop::collection::vec(cubes, 1..5).prop_map(|c| c.join(", "));
+        (any::<u32>(), prop::collection::vec(round, 1..6))
+            .prop_map(|(id, rounds)| format!("Game {id}: {}", rounds.join("; ")))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_gives_back_the_line(line in line()) {
+            let game: Game = line.parse().unwrap();
+            prop_assert_eq!(game.to_string(), line);
+        }
+    }
+}
@@ -1,134 +1,73 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    str::FromStr,
-};
-
-pub fn main() -> std::io::Result<()> {
-    let f = File::open("data/2.1.txt")?;
-    let reader: BufReader<File> = BufReader::new(f);
-    let lines = reader.lines().flatten().collect::<Vec<_>>();
-    println!("pt1: {}", pt1(&lines));
-    println!("pt2: {}", pt2(&lines));
-    Ok(())
-}
+//! `day02 [input]` prints both parts like every other day.
+//!
+//! `day02 [input] --bag "12 red, 13 green, 14 blue"` or `--bag-file <file>`
+//! checks the games against a different bag, with any colors in it: pt1
+//! against its counts, and pt2's power over its colors.
+//!
+//! `day02 [input] --from <text|json|csv>` reads a game log written some other
+//! way, and `--to <text|json|csv>` prints the games that way instead of
+//! solving them.
 
-// The Elf would first like to know which games would have been possible if the bag contained only
-// 12 red cubes, 13 green cubes, and 14 blue cubes?
+use std::{path::PathBuf, process::ExitCode};
 
-type RedCount = i32;
-type BlueCount = i32;
-type GreenCount = i32;
+use clap::Parser;
+use common::{cli::InputArgs, Solution};
+use day02::{
+    formats::{self, Format},
+    Bag, Day02,
+};
 
-#[derive(Debug, PartialEq)]
-struct Game {
-    id: i32,
-    rounds: Vec<(RedCount, GreenCount, BlueCount)>,
+/// day 2's cube games, checked against a bag or written out another way
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+    /// the bag to check the games against, like "12 red, 13 green, 14 blue"
+    #[arg(long, conflicts_with = "to")]
+    bag: Option<String>,
+    /// a file with the bag in it instead
+    #[arg(long, conflicts_with_all = ["bag", "to"])]
+    bag_file: Option<PathBuf>,
+    /// how the games are written: text, json or csv
+    #[arg(long, default_value_t = Format::Text)]
+    from: Format,
+    /// print the games as text, json or csv instead of solving them
+    #[arg(long)]
+    to: Option<Format>,
 }
 
-impl Game {
-    pub fn max(&self) -> (RedCount, GreenCount, BlueCount) {
-        let mut out = (0, 0, 0);
-        for (r, g, b) in &self.rounds {
-            let (ar, ag, ab) = out;
-            out = (*r.max(&ar), *g.max(&ag), *b.max(&ab));
-        }
-        out
+fn run(args: &Args) -> Result<(), String> {
+    let lines = args.input.read(Day02::DAY).map_err(|e| e.to_string())?;
+    let games = formats::read(&lines.join("\n"), args.from)?;
+    if let Some(format) = args.to {
+        print!("{}", formats::write(&games, format)?);
+        return Ok(());
     }
-
-    pub fn can_be_playable_with(&self, (r, g, b): (i32, i32, i32)) -> bool {
-        let (mr, mg, mb) = self.max();
-        r >= mr && g >= mg && b >= mb
-    }
-}
-
-impl FromStr for Game {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let game = s.split(":").next().unwrap();
-        let id: i32 = game.split(" ").nth(1).map(|n| n.parse().unwrap()).unwrap();
-        let rounds: Vec<(i32, i32, i32)> = s
-            .split(":")
-            .nth(1)
-            .map(|rs| {
-                let g: Vec<(i32, i32, i32)> = rs
-                    .split(";")
-                    .map(|round| {
-                        round
-                            .split(",")
-                            .map(|color| {
-                                let mut cs = color.strip_prefix(" ").unwrap().split(" ");
-                                let count = cs.next().unwrap().parse().unwrap();
-                                let name = cs.next().unwrap();
-                                let color_tuple: (i32, i32, i32) = match name {
-                                    "red" => (count, 0, 0),
-                                    "green" => (0, count, 0),
-                                    "blue" => (0, 0, count),
-                                    what => panic!("don't know this color: {}", what),
-                                };
-                                color_tuple
-                            })
-                            .reduce(|(red, green, blue), (acc_r, acc_g, acc_b)| {
-                                (red + acc_r, green + acc_g, blue + acc_b)
-                            })
-                            .unwrap()
-                    })
-                    .collect();
-                g
-            })
-            .unwrap();
-        Ok(Game { id, rounds })
-    }
-}
-
-// 12 red cubes, 13 green cubes, and 14 blue cubes
-pub fn pt1(lines: &Vec<String>) -> i32 {
-    let games = lines.iter().map(|g| g.parse::<Game>().unwrap());
-    let can_fit: Vec<_> = games
-        .filter(|g| g.can_be_playable_with((12, 13, 14)))
-        .collect();
-    can_fit.iter().map(|g| g.id).reduce(|a, b| a + b).unwrap()
-}
-
-// find the min number of cubes that could be in a bag to make the game possible
-// sum up the products of every game
-pub fn pt2(lines: &Vec<String>) -> i32 {
-    let games = lines.iter().map(|g| g.parse::<Game>().unwrap());
-    let mins = games.map(|g| g.max()).map(|(r, g, b)| r * g * b);
-    mins.reduce(|a, b| a + b).unwrap()
+    let bag = match (&args.bag, &args.bag_file) {
+        (Some(bag), _) => bag
+            .parse()
+            .map_err(|e| common::render(&e, std::slice::from_ref(bag)))?,
+        (None, Some(path)) => Bag::load(path)?,
+        (None, None) => Bag::default(),
+    };
+    println!("pt1: {}", day02::possible_with(&games, &bag));
+    let power = day02::power_with(&games, &bag)
+        .ok_or("pt2: the powers over that bag's colors add up to more than fits in a u128")?;
+    println!("pt2: {power}");
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_game_parse() {
-        let raw = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
-        let p: Game = raw.parse().unwrap();
-        assert_eq!(
-            Game {
-                id: 3,
-                rounds: vec![(20, 8, 6), (4, 13, 5), (1, 5, 0)],
-            },
-            p
-        )
-    }
-
-    #[test]
-    fn test_pt1_example() {
-        let lines: Vec<String> = vec![
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        let answer = pt1(&lines);
-        assert_eq!(8, answer);
+fn main() -> ExitCode {
+    match run(&Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // parse errors come back rendered, with their own `error:`
+            if e.starts_with("error: ") {
+                eprintln!("{e}");
+            } else {
+                eprintln!("error: {e}");
+            }
+            ExitCode::FAILURE
+        }
     }
 }
@@ -0,0 +1,244 @@
+use std::{fmt, str::FromStr};
+
+use common::{ParseError, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Card>;
+    type Answer = i32;
+    type Error = CardError;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, e)| e.parse::<Card>().map_err(|e| e.offset(i, 0)))
+            .collect()
+    }
+
+    fn part1(cards: &Self::Input) -> i32 {
+        let scores = cards.iter().map(|c| c.score());
+        scores.sum()
+    }
+
+    fn part2(cards: &Self::Input) -> i32 {
+        let mut pool = Vec::new();
+        pool.resize(cards.len(), 1);
+        for i in 0..cards.len() {
+            let matches = cards[i].matches();
+            for m in 1..=matches {
+                let count = pool[i];
+                if i + m < pool.len() {
+                    pool[i + m] += count;
+                }
+            }
+        }
+        pool.iter().sum()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    id: i32,
+    numbers: Vec<i32>,
+    winning_numbers: Vec<i32>,
+}
+
+impl Card {
+    pub fn score(&self) -> i32 {
+        let mut acc = 0;
+        for n in &self.numbers {
+            if self.winning_numbers.contains(n) {
+                acc = if acc == 0 { 1 } else { acc * 2 };
+            }
+        }
+        acc
+    }
+
+    pub fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|e| self.winning_numbers.contains(e))
+            .count()
+    }
+}
+
+/// what the card parser was looking for when it gave up
+#[derive(Debug, PartialEq)]
+pub enum CardErrorKind {
+    Header,
+    Id,
+    Separator,
+    Number,
+}
+
+impl fmt::Display for CardErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardErrorKind::Header => write!(f, "`Card <id>:`"),
+            CardErrorKind::Id => write!(f, "a card id"),
+            CardErrorKind::Separator => write!(f, "` | ` between the two lists of numbers"),
+            CardErrorKind::Number => write!(f, "a number"),
+        }
+    }
+}
+
+pub type CardError = ParseError<CardErrorKind>;
+
+impl FromStr for Card {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use CardErrorKind::*;
+        let (header, numbers_part) = s
+            .split_once(':')
+            .ok_or(CardError::new(Header, 0..s.len()))?;
+        let id_raw = header
+            .strip_prefix("Card")
+            .ok_or(CardError::new(Header, 0..header.len()))?;
+        let id = id_raw
+            .trim_start()
+            .parse()
+            .map_err(|_| CardError::new(Id, 4..header.len()))?;
+        // " 1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+        let numbers_start = header.len() + 1;
+        let (my_raw, winning_raw) = numbers_part
+            .split_once(" | ")
+            .ok_or(CardError::new(Separator, numbers_start..s.len()))?;
+
+        let numbers = parse_nums(my_raw).map_err(|e| e.offset(0, numbers_start))?;
+        let winning_start = numbers_start + my_raw.len() + 3;
+        let winning_numbers = parse_nums(winning_raw).map_err(|e| e.offset(0, winning_start))?;
+
+        Ok(Card {
+            id,
+            numbers,
+            winning_numbers,
+        })
+    }
+}
+
+fn parse_nums(line: &str) -> Result<Vec<i32>, CardError> {
+    let mut start = 0;
+    let mut end = 0;
+    let mut in_number = false;
+    let line = line.chars().collect::<Vec<_>>();
+    let mut acc = vec![];
+    let number = |start: usize, end: usize| {
+        line[start..end]
+            .iter()
+            .collect::<String>()
+            .parse::<i32>()
+            .map_err(|_| CardError::new(CardErrorKind::Number, start..end))
+    };
+    while end < line.len() {
+        if line[end].is_ascii_digit() {
+            if !in_number {
+                start = end;
+            }
+            in_number = true;
+        } else if line[end] == ' ' {
+            if in_number {
+                acc.push(number(start, end)?);
+                start = end;
+                in_number = false;
+            }
+        } else {
+            return Err(CardError::new(CardErrorKind::Number, end..end + 1));
+        }
+        end += 1;
+    }
+    if in_number {
+        acc.push(number(start, end)?);
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ex1() {
+        let input: Vec<_> = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let c: Card = input[0].parse().unwrap();
+
+        assert_eq!(
+            Card {
+                id: 1,
+                numbers: vec![41, 48, 83, 86, 17],
+                winning_numbers: vec![83, 86, 6, 31, 17, 9, 48, 53],
+            },
+            c,
+        );
+        /*
+            In the above example, card 1 has five winning numbers (41, 48, 83, 86, and 17) and
+            eight numbers you have (83, 86, 6, 31, 17, 9, 48, and 53). Of the numbers you have,
+            four of them (48, 83, 17, and 86) are winning numbers! That means card 1 is worth 8
+            points (1 for the first match, then doubled three times for each of the three matches
+            after the first).
+
+            Card 2 has two winning numbers (32 and 61), so it is worth 2 points.
+            Card 3 has two winning numbers (1 and 21), so it is worth 2 points.
+            Card 4 has one winning number (84), so it is worth 1 point.
+            Card 5 has no winning numbers, so it is worth no points.
+            Card 6 has no winning numbers, so it is worth no points.
+        */
+
+        let scores = input
+            .iter()
+            .map(|e| e.parse::<Card>().unwrap())
+            .map(|c| c.score())
+            .collect::<Vec<_>>();
+        assert_eq!(scores, vec![8, 2, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_ex2() {
+        let input: Vec<_> = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let answer = Day04::solve_part2(&input).unwrap();
+        assert_eq!(30, answer);
+    }
+
+    #[test]
+    fn test_parse_num_col() {
+        let input = " 1 21 53 59 44";
+        assert_eq!(parse_nums(input), Ok(vec![1, 21, 53, 59, 44]));
+    }
+
+    #[test]
+    fn test_card_parse_errors() {
+        let e = "Card 1: 41 48 | 83 8x".parse::<Card>().unwrap_err();
+        assert_eq!(e, CardError::new(CardErrorKind::Number, 20..21));
+
+        let e = "Card 1: 41 48 83 86".parse::<Card>().unwrap_err();
+        assert_eq!(e, CardError::new(CardErrorKind::Separator, 7..19));
+
+        let e = "Card x: 41 | 83".parse::<Card>().unwrap_err();
+        assert_eq!(e, CardError::new(CardErrorKind::Id, 4..6));
+    }
+}
@@ -0,0 +1,515 @@
+//! pt2 the slow way, for checking `pt2_ranges`: every seed walked to its
+//! location, with the seeds cut into chunks shared out between threads.
+//!
+//! the real input has about 2 billion seeds, which takes a few minutes on a
+//! release build. it reports how far it's got, stops between seeds when
+//! asked, and with a checkpoint file picks up again from the last chunks it
+//! finished.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use intervals::{Interval, IntervalSet};
+
+use crate::{Almanac, AlmanacMap, SeedList};
+
+/// how often `run` reports progress
+const REPORT_EVERY: Duration = Duration::from_secs(1);
+/// seeds a thread walks between looking at the counters
+const STRIDE: u64 = 1 << 16;
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub threads: usize,
+    /// seeds per piece of work. the checkpoint only saves whole chunks
+    pub chunk_size: u64,
+    pub checkpoint: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            chunk_size: 1 << 22,
+            checkpoint: None,
+        }
+    }
+}
+
+/// how far `run` has got
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    /// seeds walked, including ones from the checkpoint
+    pub done: u64,
+    pub total: u64,
+    /// seeds the checkpoint already had when this run started
+    pub resumed: u64,
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// how much longer at the rate this run has managed so far
+    pub fn eta(&self) -> Option<Duration> {
+        let walked = self.done - self.resumed;
+        if walked == 0 {
+            return None;
+        }
+        let left = (self.total - self.done) as f64;
+        Some(self.elapsed.mul_f64(left / walked as f64))
+    }
+}
+
+/// `1h02m03s`, leaving off the hours (and minutes) when there are none
+fn hms(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (h, m) {
+        (0, 0) => format!("{s}s"),
+        (0, m) => format!("{m}m{s:02}s"),
+        (h, m) => format!("{h}h{m:02}m{s:02}s"),
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = self.done as f64 / self.total.max(1) as f64 * 100.0;
+        write!(
+            f,
+            "progress: {percent:.4}% ({} of {} seeds), eta ",
+            self.done, self.total
+        )?;
+        match self.eta() {
+            Some(eta) => write!(f, "{}", hms(eta)),
+            None => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// the lowest location of any seed
+    Finished(u64),
+    /// stopped early. `saved` seeds are in whole chunks that made it to the
+    /// checkpoint, or would have if there was one
+    Cancelled { saved: u64, total: u64 },
+}
+
+/// the seed ranges cut up into runs of at most `size` seeds
+fn chunks(seed_ranges: &IntervalSet, size: u64) -> Vec<Interval> {
+    let mut chunks = vec![];
+    for range in seed_ranges {
+        let mut rest = Some(*range);
+        while let Some(range) = rest {
+            let cut = range.start().saturating_add(size).min(range.end());
+            let (chunk, after) = range.split_at(cut);
+            chunks.extend(chunk);
+            rest = after;
+        }
+    }
+    chunks
+}
+
+/// the chunks a run has finished, and what it was running on. on disk:
+///
+/// ```text
+/// # day05 brute force checkpoint, then `chunk lowest-location` per chunk done
+/// chunk_size 4194304
+/// seeds 79 14 55 13
+/// 0 82
+/// ```
+#[derive(Debug, PartialEq)]
+struct Checkpoint {
+    chunk_size: u64,
+    seeds: Vec<u64>,
+    done: BTreeMap<usize, u64>,
+}
+
+const HEADER: &str = "# day05 brute force checkpoint, then `chunk lowest-location` per chunk done";
+
+impl Checkpoint {
+    fn header(&self) -> String {
+        let seeds: Vec<String> = self.seeds.iter().map(u64::to_string).collect();
+        format!(
+            "{HEADER}\nchunk_size {}\nseeds {}\n",
+            self.chunk_size,
+            seeds.join(" ")
+        )
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.split_inclusive('\n').map(str::trim_end);
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let chunk_size = lines.next()?.strip_prefix("chunk_size ")?.parse().ok()?;
+        let seeds = lines.next()?.strip_prefix("seeds")?;
+        let seeds = seeds
+            .split_whitespace()
+            .map(|s| s.parse().ok())
+            .collect::<Option<_>>()?;
+        let mut done = BTreeMap::new();
+        // a last line without its newline was cut off part way, so that
+        // chunk gets done again
+        let finished = text
+            .split_inclusive('\n')
+            .skip(3)
+            .filter(|l| l.ends_with('\n'));
+        for line in finished {
+            let (chunk, lowest) = line.trim_end().split_once(' ')?;
+            done.insert(chunk.parse().ok()?, lowest.parse().ok()?);
+        }
+        Some(Self {
+            chunk_size,
+            seeds,
+            done,
+        })
+    }
+
+    /// what's at `path` if it's for this run, a fresh one if there's nothing
+    /// there, and an error if it's for some other run
+    fn load(path: &PathBuf, chunk_size: u64, seeds: &[u64]) -> Result<Self, String> {
+        let fresh = Self {
+            chunk_size,
+            seeds: seeds.to_vec(),
+            done: BTreeMap::new(),
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(_) if !path.exists() => {
+                fs::write(path, fresh.header())
+                    .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+                return Ok(fresh);
+            }
+            Err(e) => return Err(format!("could not read {}: {e}", path.display())),
+        };
+        let checkpoint =
+            Self::parse(&text).ok_or(format!("{} isn't a day05 checkpoint", path.display()))?;
+        if checkpoint.header() != fresh.header() {
+            return Err(format!(
+                "{} is from a run with other seeds or chunk size",
+                path.display()
+            ));
+        }
+        if !text.ends_with('\n') {
+            // drop the cut off line, so the next chunk starts a line of its
+            // own and nothing reads what's left of it as finished
+            let whole = text.rfind('\n').map_or(0, |i| i + 1);
+            OpenOptions::new()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_len(whole as u64))
+                .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// the lowest location of any seed in any of pt2's ranges, walking every one
+/// of them. `report` hears how it's going every so often, and once more if
+/// it gets to the end.
+/// once `cancel` is set the threads stop and the chunks they were partway
+/// through are thrown away
+pub fn run(
+    almanac: &Almanac,
+    options: &Options,
+    cancel: &AtomicBool,
+    mut report: impl FnMut(&Progress),
+) -> Result<Outcome, String> {
+    assert!(options.chunk_size > 0, "chunks need some seeds in them");
+    let total = SeedList::new(&almanac.seed_ranges).remaining();
+    let chunks = chunks(&almanac.seed_ranges, options.chunk_size);
+    let mut checkpoint = match &options.checkpoint {
+        Some(path) => Checkpoint::load(path, options.chunk_size, &almanac.seeds)?,
+        None => Checkpoint {
+            chunk_size: options.chunk_size,
+            seeds: almanac.seeds.clone(),
+            done: BTreeMap::new(),
+        },
+    };
+    let mut file = match &options.checkpoint {
+        Some(path) => Some(
+            OpenOptions::new()
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("could not open {}: {e}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let saved = |checkpoint: &Checkpoint| {
+        checkpoint
+            .done
+            .keys()
+            .filter_map(|&i| chunks.get(i))
+            .map(Interval::len)
+            .sum::<u64>()
+    };
+    let resumed = saved(&checkpoint);
+    let todo: Vec<usize> = (0..chunks.len())
+        .filter(|i| !checkpoint.done.contains_key(i))
+        .collect();
+    let maps: Vec<&AlmanacMap> = almanac.seed_to_location_maps().collect();
+    let next = AtomicUsize::new(0);
+    let walked = AtomicU64::new(resumed);
+    let start = Instant::now();
+    let progress = |walked: &AtomicU64| Progress {
+        done: walked.load(Ordering::Relaxed),
+        total,
+        resumed,
+        elapsed: start.elapsed(),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let written = thread::scope(|s| {
+        for _ in 0..options.threads.max(1) {
+            let tx = tx.clone();
+            let (todo, chunks, maps) = (&todo, &chunks, &maps);
+            let (next, walked) = (&next, &walked);
+            s.spawn(move || {
+                while !cancel.load(Ordering::Relaxed) {
+                    let Some(&i) = todo.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                        return;
+                    };
+                    let mut lowest = u64::MAX;
+                    let mut since = 0;
+                    for seed in SeedList::new(&chunks[i].into()) {
+                        let location = maps.iter().fold(seed, |n, map| map.source_to_dest(&n));
+                        lowest = lowest.min(location);
+                        since += 1;
+                        if since == STRIDE {
+                            walked.fetch_add(since, Ordering::Relaxed);
+                            since = 0;
+                            if cancel.load(Ordering::Relaxed) {
+                                return;
+                            }
+                        }
+                    }
+                    walked.fetch_add(since, Ordering::Relaxed);
+                    if tx.send((i, lowest)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // keep the checkpoint up to date as chunks come in
+        loop {
+            match rx.recv_timeout(REPORT_EVERY) {
+                Ok((i, lowest)) => {
+                    checkpoint.done.insert(i, lowest);
+                    if let Some(file) = &mut file {
+                        writeln!(file, "{i} {lowest}").map_err(|e| e.to_string())?;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => report(&progress(&walked)),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+    });
+    written.map_err(|e: String| format!("could not save checkpoint: {e}"))?;
+
+    if checkpoint.done.len() < chunks.len() {
+        let saved = saved(&checkpoint);
+        return Ok(Outcome::Cancelled { saved, total });
+    }
+    report(&progress(&walked));
+    let lowest = checkpoint.done.values().min().copied();
+    Ok(Outcome::Finished(lowest.expect("pt2 has some seeds")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use common::Solution;
+
+    use super::*;
+    use crate::{generate, Day05};
+
+    fn example() -> Almanac {
+        let lines: Vec<String> = crate::tests::EXAMPLE.lines().map(String::from).collect();
+        Day05::parse(&lines).unwrap()
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("day05-brute-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn options(chunk_size: u64, checkpoint: Option<PathBuf>) -> Options {
+        Options {
+            threads: 3,
+            chunk_size,
+            checkpoint,
+        }
+    }
+
+    #[test]
+    fn test_chunks_cover_the_seeds() {
+        let seeds: IntervalSet = [Interval::new(0, 10), Interval::new(20, 23)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            format!("{:?}", chunks(&seeds, 4)),
+            "[0..4, 4..8, 8..10, 20..23]"
+        );
+    }
+
+    #[test]
+    fn test_matches_ranges() {
+        let almanac = example();
+        let never = AtomicBool::new(false);
+        for chunk_size in [1, 5, 100] {
+            let got = run(&almanac, &options(chunk_size, None), &never, |_| {});
+            assert_eq!(got, Ok(Outcome::Finished(46)));
+        }
+
+        let params = generate::Params {
+            seed_range_len: 5000,
+            ..Default::default()
+        };
+        let lines: Vec<String> = generate::generate(&params, 1)
+            .lines()
+            .map(String::from)
+            .collect();
+        let almanac = Day05::parse(&lines).unwrap();
+        let mut last = None;
+        let got = run(&almanac, &options(777, None), &never, |p| {
+            last = Some(p.clone())
+        });
+        assert_eq!(got, Ok(Outcome::Finished(Day05::part2(&almanac))));
+        let last = last.unwrap();
+        assert_eq!(last.done, last.total);
+        assert_eq!(last.total, almanac.seed_ranges.len());
+    }
+
+    #[test]
+    fn test_cancel_and_resume() {
+        let almanac = example();
+        let path = scratch("resume");
+
+        // cancelled before it starts, nothing gets done but the file is there
+        let cancelled = AtomicBool::new(true);
+        let got = run(
+            &almanac,
+            &options(5, Some(path.clone())),
+            &cancelled,
+            |_| {},
+        );
+        assert_eq!(
+            got,
+            Ok(Outcome::Cancelled {
+                saved: 0,
+                total: 27
+            })
+        );
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, format!("{HEADER}\nchunk_size 5\nseeds 79 14 55 13\n"));
+
+        // as if an earlier run got some of the way, with a wrong answer for
+        // chunk 0 to show it isn't walked again
+        fs::write(&path, text + "0 1\n3 2000\n4 2").unwrap();
+        let never = AtomicBool::new(false);
+        let mut first = None;
+        let got = run(&almanac, &options(5, Some(path.clone())), &never, |p| {
+            first.get_or_insert(p.resumed);
+        });
+        assert_eq!(got, Ok(Outcome::Finished(1)));
+        // chunk 4 wasn't finished writing, so it's done again
+        assert_eq!(first, Some(5 + 5));
+        let done = Checkpoint::parse(&fs::read_to_string(&path).unwrap())
+            .unwrap()
+            .done;
+        assert_eq!(done.len(), 6);
+        assert_eq!(done[&3], 2000);
+
+        // other chunks, other checkpoint
+        let e = run(&almanac, &options(6, Some(path.clone())), &never, |_| {}).unwrap_err();
+        assert!(e.contains("other seeds or chunk size"), "{e}");
+        fs::write(&path, "hello").unwrap();
+        let e = run(&almanac, &options(5, Some(path.clone())), &never, |_| {}).unwrap_err();
+        assert!(e.contains("isn't a day05 checkpoint"), "{e}");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_interrupted_twice() {
+        let params = generate::Params {
+            seed_range_len: 5000,
+            ..Default::default()
+        };
+        let lines: Vec<String> = generate::generate(&params, 2)
+            .lines()
+            .map(String::from)
+            .collect();
+        let almanac = Day05::parse(&lines).unwrap();
+        let answer = Day05::part2(&almanac);
+        assert!(answer > 9, "a cut off lowest has to be able to beat it");
+        let path = scratch("twice");
+        let never = AtomicBool::new(false);
+        let cancelled = AtomicBool::new(true);
+        let run_with = |cancel| run(&almanac, &options(777, Some(path.clone())), cancel, |_| {});
+
+        run_with(&never).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let records: Vec<&str> = text.lines().skip(3).collect();
+        assert!(records.len() > 4);
+        // cut off part way through a line, leaving the first digit of its
+        // lowest location
+        let kept = text.lines().take(3 + records.len() / 2).collect::<Vec<_>>();
+        let whole = kept.join("\n") + "\n";
+        let (chunk, lowest) = records[records.len() / 2].split_once(' ').unwrap();
+        fs::write(&path, format!("{whole}{chunk} {}", &lowest[..1])).unwrap();
+
+        // interrupted again before finishing anything
+        let got = run_with(&cancelled).unwrap();
+        assert!(matches!(got, Outcome::Cancelled { .. }), "{got:?}");
+        assert_eq!(fs::read_to_string(&path).unwrap(), whole);
+
+        // and again part way through writing the next line
+        fs::write(&path, format!("{whole}{chunk}")).unwrap();
+        assert_eq!(run_with(&never), Ok(Outcome::Finished(answer)));
+        let done = Checkpoint::parse(&fs::read_to_string(&path).unwrap())
+            .unwrap()
+            .done;
+        assert_eq!(done.len(), records.len());
+        assert_eq!(done.values().min(), Some(&answer));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress {
+            done: 750,
+            total: 1000,
+            resumed: 500,
+            elapsed: Duration::from_secs(3725),
+        };
+        assert_eq!(progress.eta(), Some(Duration::from_secs(3725)));
+        assert_eq!(
+            progress.to_string(),
+            "progress: 75.0000% (750 of 1000 seeds), eta 1h02m05s"
+        );
+        let started = Progress {
+            done: 500,
+            ..progress
+        };
+        assert_eq!(started.eta(), None);
+        assert!(started.to_string().ends_with("eta unknown"));
+        assert_eq!(hms(Duration::from_secs(65)), "1m05s");
+        assert_eq!(hms(Duration::from_secs(9)), "9s");
+    }
+}
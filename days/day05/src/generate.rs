@@ -0,0 +1,301 @@
+//! made-up almanacs, far bigger than the example if need be, and a slow but
+//! obvious solver to check the range code's answers on them.
+
+use std::fmt::Write as _;
+
+use fastrand::Rng;
+
+use crate::{Almanac, AlmanacMap, MappingLine, LOCATION, SEED};
+
+/// the shape of the almanacs `generate` makes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Params {
+    /// categories from `seed` to `location`, both included, so at least 2
+    pub categories: usize,
+    pub lines_per_map: usize,
+    /// pairs on the seeds line
+    pub seed_ranges: usize,
+    /// the longest a seed range gets. `reference` walks every seed, so keep
+    /// it small when checking answers
+    pub seed_range_len: u64,
+    /// roughly how much of `0..max` the mapping lines cover, from 0 to 1.
+    /// the rest passes through, so the higher this is the more the seed
+    /// ranges get moved and split up
+    pub density: f64,
+    /// the chance, from 0 to 1, that a line gets a second one whose source
+    /// range overlaps it, sending some of the same values somewhere else.
+    /// whichever is listed first maps them
+    pub overlap: f64,
+    /// every value is below this, which can be as big as `u64::MAX`
+    pub max: u64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            categories: 8,
+            lines_per_map: 10,
+            seed_ranges: 4,
+            seed_range_len: 100,
+            density: 0.5,
+            overlap: 0.0,
+            max: 1 << 32,
+        }
+    }
+}
+
+/// `total` cut into `parts` random amounts, which can be 0
+fn split(rng: &mut Rng, total: u64, parts: usize) -> Vec<u64> {
+    let mut cuts: Vec<u64> = (1..parts).map(|_| rng.u64(0..=total)).collect();
+    cuts.sort_unstable();
+    cuts.push(total);
+    let mut last = 0;
+    cuts.into_iter()
+        .map(|cut| {
+            let part = cut - last;
+            last = cut;
+            part
+        })
+        .collect()
+}
+
+/// the lines of one map. they cover a few blocks of `0..max` with gaps in
+/// between, and each block's lines are its own pieces shuffled, so every
+/// value they move lands on another value they move and `check` has nothing
+/// to warn about. unless `params.overlap` adds lines on top, see `overlapping`
+fn mapping(rng: &mut Rng, params: &Params) -> Vec<MappingLine> {
+    let max = params.max;
+    let lines = params
+        .lines_per_map
+        .min(max.try_into().unwrap_or(usize::MAX));
+    if lines == 0 {
+        return vec![];
+    }
+    let covered = ((max as f64 * params.density) as u64).clamp(lines as u64, max);
+    // at least two lines a block where possible, a lone line can only map
+    // its block to itself
+    let blocks = rng.usize(1..=(lines / 2).max(1));
+    let lines_in: Vec<u64> = split(rng, (lines - blocks) as u64, blocks)
+        .into_iter()
+        .map(|n| n + 1)
+        .collect();
+    let spare = covered - lines as u64;
+    let block_lens = split(rng, spare, blocks)
+        .into_iter()
+        .zip(&lines_in)
+        .map(|(len, lines)| len + lines);
+    let gaps = split(rng, max - covered, blocks + 1);
+
+    let mut mapping = vec![];
+    let mut start = 0;
+    for ((len, lines), gap) in block_lens.zip(&lines_in).zip(gaps) {
+        start += gap;
+        let pieces: Vec<u64> = split(rng, len - lines, *lines as usize)
+            .into_iter()
+            .map(|n| n + 1)
+            .collect();
+        let mut order: Vec<usize> = (0..pieces.len()).collect();
+        rng.shuffle(&mut order);
+        if order.is_sorted() {
+            order.rotate_left(1);
+        }
+        let mut sources = vec![];
+        let mut source_start = start;
+        for length in &pieces {
+            sources.push(source_start);
+            source_start += length;
+        }
+        let mut destination_start = start;
+        for i in order {
+            mapping.push(MappingLine {
+                destination_start,
+                source_start: sources[i],
+                length: pieces[i],
+            });
+            destination_start += pieces[i];
+        }
+        start += len;
+    }
+    for i in 0..mapping.len() {
+        if rng.f64() < params.overlap {
+            let line = overlapping(rng, &mapping[i], max);
+            mapping.push(line);
+        }
+    }
+    rng.shuffle(&mut mapping);
+    mapping
+}
+
+/// a line whose source range starts inside `line`'s and whose destination is
+/// anywhere below `max`
+fn overlapping(rng: &mut Rng, line: &MappingLine, max: u64) -> MappingLine {
+    let source_start = rng.u64(line.source_start..line.source().end());
+    let length = rng.u64(1..=line.length.min(max - source_start));
+    MappingLine {
+        destination_start: rng.u64(0..=max - length),
+        source_start,
+        length,
+    }
+}
+
+/// an almanac with the shape in `params`, the same one every time for the
+/// same `seed`. the maps are listed in a random order
+pub fn generate(params: &Params, seed: u64) -> String {
+    assert!(params.categories >= 2, "need at least seed and location");
+    assert!(params.seed_ranges >= 1, "need some seeds");
+    assert!(params.max > 0, "need some values to map");
+    let mut rng = Rng::with_seed(seed);
+
+    let mut out = String::from("seeds:");
+    for _ in 0..params.seed_ranges {
+        let len = rng.u64(1..=params.seed_range_len.clamp(1, params.max));
+        let start = rng.u64(0..=params.max - len);
+        let _ = write!(out, " {start} {len}");
+    }
+
+    let names: Vec<String> = (0..params.categories)
+        .map(|i| match i {
+            0 => SEED.to_string(),
+            i if i == params.categories - 1 => LOCATION.to_string(),
+            i => format!("category{i}"),
+        })
+        .collect();
+    let mut maps: Vec<AlmanacMap> = names
+        .windows(2)
+        .map(|pair| AlmanacMap::new(&pair[0], &pair[1], mapping(&mut rng, params)))
+        .collect();
+    rng.shuffle(&mut maps);
+    for map in maps {
+        let _ = write!(out, "\n\n{map}");
+    }
+    out.push('\n');
+    out
+}
+
+/// both parts' answers worked out the slow way, without any of the interval
+/// code: every seed, including every one in pt2's ranges, walked through the
+/// maps one at a time, scanning each map for its line.
+///
+/// it follows the first map out of each category, which is the only one in
+/// almanacs from `generate`
+pub fn reference(almanac: &Almanac) -> (u64, u64) {
+    let location = |seed: u64| {
+        let (mut category, mut n) = (SEED, seed);
+        while category != LOCATION {
+            let map = almanac
+                .maps
+                .iter()
+                .find(|m| m.source_name == category)
+                .expect("checked when parsing");
+            let line = map
+                .mapping
+                .iter()
+                .find(|l| l.source_start <= n && n - l.source_start < l.length);
+            if let Some(line) = line {
+                n = n - line.source_start + line.destination_start;
+            }
+            category = &map.destination_name;
+        }
+        n
+    };
+    let part1 = almanac.seeds.iter().map(|&s| location(s)).min().unwrap();
+    let part2 = almanac
+        .seeds
+        .chunks_exact(2)
+        .flat_map(|pair| (0..pair[1]).map(move |i| pair[0] + i))
+        .map(location)
+        .min()
+        .unwrap();
+    (part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::{Day05, FindingKind};
+
+    fn parse(text: &str) -> Almanac {
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        match Day05::parse(&lines) {
+            Ok(almanac) => almanac,
+            Err(e) => panic!("{}\n{text}", common::render(&e, &lines)),
+        }
+    }
+
+    #[test]
+    fn test_reference_on_the_example() {
+        let example = parse(crate::tests::EXAMPLE);
+        assert_eq!(reference(&example), (35, 46));
+    }
+
+    #[test]
+    fn test_generate_has_the_shape_asked_for() {
+        let params = Params {
+            categories: 4,
+            lines_per_map: 6,
+            seed_ranges: 3,
+            ..Params::default()
+        };
+        let text = generate(&params, 7);
+        assert_eq!(text, generate(&params, 7));
+        assert_ne!(text, generate(&params, 8));
+
+        let almanac = parse(&text);
+        assert_eq!(almanac.seeds.len(), 6);
+        assert_eq!(almanac.maps.len(), 3);
+        assert!(almanac.maps.iter().all(|m| m.mapping.len() == 6));
+        assert_eq!(almanac.path(SEED, LOCATION).unwrap().len(), 3);
+
+        let covered = |density| {
+            let params = Params {
+                density,
+                ..params.clone()
+            };
+            let almanac = parse(&generate(&params, 7));
+            let map = &almanac.maps[0];
+            map.mapping.iter().map(|l| l.length).sum::<u64>()
+        };
+        assert_eq!(covered(0.0), 6);
+        assert_eq!(covered(0.25), 1 << 30);
+        assert_eq!(covered(1.0), 1 << 32);
+
+        let params = Params {
+            overlap: 1.0,
+            ..params
+        };
+        let almanac = parse(&generate(&params, 7));
+        assert!(almanac.maps.iter().all(|m| m.mapping.len() == 12));
+        assert!(almanac
+            .check()
+            .iter()
+            .any(|f| matches!(f.kind, FindingKind::SourceOverlap(_))));
+    }
+
+    /// the range code against `reference` on lots of small and not so small
+    /// almanacs
+    #[test]
+    fn test_fuzz_against_reference() {
+        let mut rng = Rng::with_seed(5);
+        for seed in 0..300 {
+            let params = Params {
+                categories: rng.usize(2..10),
+                lines_per_map: rng.usize(0..12),
+                seed_ranges: rng.usize(1..5),
+                seed_range_len: rng.u64(1..200),
+                density: rng.f64(),
+                overlap: if rng.bool() { rng.f64() } else { 0.0 },
+                max: match rng.u8(0..3) {
+                    0 => rng.u64(1..1000),
+                    1 => rng.u64(1..1 << 40),
+                    _ => u64::MAX,
+                },
+            };
+            let text = generate(&params, seed);
+            let almanac = parse(&text);
+            let got = (Day05::part1(&almanac), Day05::part2(&almanac));
+            assert_eq!(got, reference(&almanac), "{params:?}\n{text}");
+        }
+    }
+}
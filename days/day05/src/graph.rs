@@ -0,0 +1,396 @@
+//! pictures of an almanac: which ranges each map sends where, one column per
+//! category, as graphviz DOT or a layered SVG. the ranges the seeds pass
+//! through are highlighted.
+
+use std::{collections::BTreeSet, fmt::Write as _};
+
+use intervals::{Interval, IntervalSet};
+
+use crate::{Almanac, SEED};
+
+/// an almanac laid out in columns
+struct Layout {
+    /// every category, with each map going from an earlier one to a later one
+    categories: Vec<String>,
+    /// the ranges drawn in each category, in value order
+    nodes: Vec<Vec<Interval>>,
+    /// the values in each category that came from the seed ranges
+    seeds: Vec<IntervalSet>,
+    edges: Vec<Edge>,
+}
+
+/// a range of one category sent to a range of another
+struct Edge {
+    /// `(category, node)` at each end
+    from: (usize, usize),
+    to: (usize, usize),
+    /// the almanac line that does it, counting from 1, or `None` for seeds
+    /// that pass through unchanged
+    line: Option<usize>,
+    /// the seeds' values among the ones it moves
+    seeds: IntervalSet,
+}
+
+impl Layout {
+    fn new(almanac: &Almanac) -> Self {
+        let categories = almanac.categories_in_order();
+        let index = |category: &str| categories.iter().position(|c| c == category).unwrap();
+
+        let mut seeds = vec![IntervalSet::new(); categories.len()];
+        seeds[0] = almanac.seed_ranges.clone();
+        let mut nodes = vec![BTreeSet::new(); categories.len()];
+        // edges with ranges rather than node numbers until every node is in
+        let mut edges = vec![];
+        for (from, category) in categories.iter().enumerate() {
+            for i in almanac.maps_from(category) {
+                let map = &almanac.maps[i];
+                let to = index(&map.destination_name);
+                let here = seeds[from].clone();
+                for (n, line) in map.mapping.iter().enumerate() {
+                    nodes[from].insert(line.source());
+                    nodes[to].insert(line.destination());
+                    let carried = here.intersection(&map.claimed(n));
+                    let line_number = Some(map.line_of(n) + 1);
+                    edges.push((
+                        from,
+                        line.source(),
+                        to,
+                        line.destination(),
+                        line_number,
+                        carried,
+                    ));
+                }
+                let sources = map.mapping.iter().map(|l| l.source()).collect();
+                for &passing in &here.difference(&sources) {
+                    nodes[from].insert(passing);
+                    nodes[to].insert(passing);
+                    edges.push((from, passing, to, passing, None, passing.into()));
+                }
+                seeds[to] = seeds[to].union(&map.map_set(&here));
+            }
+        }
+
+        let nodes: Vec<Vec<Interval>> = nodes.into_iter().map(Vec::from_iter).collect();
+        let node = |category: usize, range: Interval| {
+            (category, nodes[category].binary_search(&range).unwrap())
+        };
+        let edges = edges
+            .into_iter()
+            .map(|(from, source, to, destination, line, seeds)| Edge {
+                from: node(from, source),
+                to: node(to, destination),
+                line,
+                seeds,
+            })
+            .collect();
+        Self {
+            categories,
+            nodes,
+            seeds,
+            edges,
+        }
+    }
+
+    /// whether any seeds pass through `category`'s `node`th range
+    fn has_seeds(&self, (category, node): (usize, usize)) -> bool {
+        let range = IntervalSet::from(self.nodes[category][node]);
+        !self.seeds[category].intersection(&range).is_empty()
+    }
+}
+
+impl Edge {
+    fn label(&self) -> String {
+        let mut label = match self.line {
+            Some(line) => format!("line {line}"),
+            None => "passes through".to_string(),
+        };
+        if !self.seeds.is_empty() {
+            let _ = write!(label, "\nseeds {:?}", self.seeds);
+        }
+        label
+    }
+}
+
+/// `s` as a DOT string
+fn quoted(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// `s` safe to put in SVG text
+fn escaped(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HIGHLIGHT: &str = "#d62728";
+const HIGHLIGHT_FILL: &str = "#ffd9a0";
+
+// svg sizes, in pixels
+const BOX_WIDTH: usize = 170;
+const BOX_HEIGHT: usize = 22;
+const ROW: usize = 30;
+const COLUMN_GAP: usize = 130;
+const MARGIN: usize = 20;
+const TOP: usize = 50;
+
+impl Almanac {
+    /// every category, `seed` first and each map's source before its
+    /// destination
+    fn categories_in_order(&self) -> Vec<String> {
+        // everything is reachable from seed and there are no loops, so
+        // taking categories once nothing leads into them gets all of them
+        let mut into: Vec<(&str, usize)> = vec![(SEED, 0)];
+        for map in &self.maps {
+            match into.iter_mut().find(|(c, _)| *c == map.destination_name) {
+                Some((_, n)) => *n += 1,
+                None => into.push((&map.destination_name, 1)),
+            }
+        }
+        let mut order = vec![SEED.to_string()];
+        let mut next = 0;
+        while let Some(category) = order.get(next).cloned() {
+            for i in self.maps_from(&category) {
+                let to = &self.maps[i].destination_name;
+                let (_, n) = into.iter_mut().find(|(c, _)| c == to).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    order.push(to.clone());
+                }
+            }
+            next += 1;
+        }
+        order
+    }
+
+    /// the almanac as a graphviz digraph: a cluster per category holding the
+    /// ranges its maps read or write, and an edge per mapping line. seeds
+    /// that no line moves get a dashed edge to the same range
+    pub fn to_dot(&self) -> String {
+        let layout = Layout::new(self);
+        let mut out = String::from("digraph almanac {\n    rankdir=LR;\n");
+        out += "    node [shape=box, fontname=\"monospace\"];\n";
+        for (c, category) in layout.categories.iter().enumerate() {
+            let _ = writeln!(out, "    subgraph cluster_{c} {{");
+            let _ = writeln!(out, "        label={};", quoted(category));
+            for (n, range) in layout.nodes[c].iter().enumerate() {
+                let style = if layout.has_seeds((c, n)) {
+                    format!(", style=filled, fillcolor=\"{HIGHLIGHT_FILL}\"")
+                } else {
+                    String::new()
+                };
+                let _ = writeln!(out, "        n{c}_{n} [label=\"{range:?}\"{style}];");
+            }
+            out += "    }\n";
+        }
+        for edge in &layout.edges {
+            let mut attributes = vec![format!("label={}", quoted(&edge.label()))];
+            if !edge.seeds.is_empty() {
+                attributes.push(format!("color=\"{HIGHLIGHT}\", penwidth=2"));
+            }
+            if edge.line.is_none() {
+                attributes.push("style=dashed".to_string());
+            }
+            let ((a, i), (b, j)) = (edge.from, edge.to);
+            let _ = writeln!(out, "    n{a}_{i} -> n{b}_{j} [{}];", attributes.join(", "));
+        }
+        out += "}\n";
+        out
+    }
+
+    /// the same picture as `to_dot`, laid out without graphviz: a column per
+    /// category with its ranges stacked in value order, and a curve per edge
+    pub fn to_svg(&self) -> String {
+        let layout = Layout::new(self);
+        let x = |category: usize| MARGIN + category * (BOX_WIDTH + COLUMN_GAP);
+        let y = |node: usize| TOP + node * ROW;
+        let rows = layout.nodes.iter().map(Vec::len).max().unwrap_or(0);
+        let width = x(layout.categories.len()) - COLUMN_GAP + MARGIN;
+        let height = y(rows) + MARGIN;
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             font-family=\"monospace\" font-size=\"12\">\n"
+        );
+        for (c, category) in layout.categories.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  <text x=\"{}\" y=\"{}\" font-weight=\"bold\">{}</text>",
+                x(c),
+                TOP - 20,
+                escaped(category)
+            );
+        }
+        // edges first so the boxes sit on top of them
+        for edge in &layout.edges {
+            let ((a, i), (b, j)) = (edge.from, edge.to);
+            let (x1, y1) = (x(a) + BOX_WIDTH, y(i) + BOX_HEIGHT / 2);
+            let (x2, y2) = (x(b), y(j) + BOX_HEIGHT / 2);
+            let bend = (x1 + x2) / 2;
+            let (stroke, stroke_width) = if edge.seeds.is_empty() {
+                ("#999999", 1)
+            } else {
+                (HIGHLIGHT, 2)
+            };
+            let dashes = if edge.line.is_none() {
+                " stroke-dasharray=\"6 4\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "  <path d=\"M {x1} {y1} C {bend} {y1}, {bend} {y2}, {x2} {y2}\" fill=\"none\" \
+                 stroke=\"{stroke}\" stroke-width=\"{stroke_width}\"{dashes}><title>{}</title></path>",
+                escaped(&edge.label())
+            );
+        }
+        for (c, nodes) in layout.nodes.iter().enumerate() {
+            for (n, range) in nodes.iter().enumerate() {
+                let fill = if layout.has_seeds((c, n)) {
+                    HIGHLIGHT_FILL
+                } else {
+                    "#f4f4f4"
+                };
+                let _ = writeln!(
+                    out,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{BOX_WIDTH}\" height=\"{BOX_HEIGHT}\" \
+                     fill=\"{fill}\" stroke=\"#333333\"/>",
+                    x(c),
+                    y(n)
+                );
+                let _ = writeln!(
+                    out,
+                    "  <text x=\"{}\" y=\"{}\">{range:?}</text>",
+                    x(c) + 6,
+                    y(n) + 15
+                );
+            }
+        }
+        out += "</svg>\n";
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::Day05;
+
+    fn example() -> Almanac {
+        let lines: Vec<String> = crate::tests::EXAMPLE.lines().map(String::from).collect();
+        Day05::parse(&lines).unwrap()
+    }
+
+    #[test]
+    fn test_layout_follows_the_seeds() {
+        let almanac = example();
+        let layout = Layout::new(&almanac);
+        assert_eq!(
+            layout.categories,
+            [
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ]
+        );
+        assert_eq!(layout.seeds[0], almanac.seed_ranges);
+        assert_eq!(layout.seeds[7], almanac.locations_of(&almanac.seed_ranges),);
+        // 52 50 48 carries both seed ranges, 50 98 2 neither
+        let carried: Vec<_> = layout
+            .edges
+            .iter()
+            .filter(|e| e.from.0 == 0)
+            .map(|e| (e.line, format!("{:?}", e.seeds)))
+            .collect();
+        assert_eq!(
+            carried,
+            [
+                (Some(4), "{}".to_string()),
+                (Some(5), "{55..68, 79..93}".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seeds_passing_through_get_their_own_edge() {
+        let lines: Vec<String> = "seeds: 0 5\n\nseed-to-location map:\n10 3 4\n3 10 4"
+            .lines()
+            .map(String::from)
+            .collect();
+        let layout = Layout::new(&Day05::parse(&lines).unwrap());
+        assert_eq!(
+            format!("{:?}", layout.nodes),
+            "[[0..3, 3..7, 10..14], [0..3, 3..7, 10..14]]"
+        );
+        let edges: Vec<_> = layout
+            .edges
+            .iter()
+            .map(|e| (e.from, e.to, e.line, format!("{:?}", e.seeds)))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                ((0, 1), (1, 2), Some(4), "{3..5}".to_string()),
+                ((0, 2), (1, 1), Some(5), "{}".to_string()),
+                ((0, 0), (1, 0), None, "{0..3}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = example().to_dot();
+        assert!(dot.starts_with("digraph almanac {\n"), "{dot}");
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("subgraph cluster_").count(), 8);
+        assert!(
+            dot.contains("        label=\"seed\";\n        n0_0 [label=\"50..98\", style=filled")
+        );
+        assert!(dot.contains("n0_1 [label=\"98..100\"];"), "{dot}");
+        assert!(dot.contains(
+            "n0_0 -> n1_4 [label=\"line 5\\nseeds {55..68, 79..93}\", color=\"#d62728\", penwidth=2];"
+        ), "{dot}");
+        // one edge per mapping line, plus the pass-throughs
+        let lines = example()
+            .maps
+            .iter()
+            .map(|m| m.mapping.len())
+            .sum::<usize>();
+        let passing = dot.matches("style=dashed").count();
+        assert_eq!(dot.matches(" -> ").count(), lines + passing);
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = example().to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(
+            svg.matches("<path ").count(),
+            Layout::new(&example()).edges.len()
+        );
+        assert_eq!(
+            svg.matches("<path ").count(),
+            svg.matches("</path>").count()
+        );
+        assert!(svg.contains(">humidity</text>"));
+        assert!(svg.contains("<title>line 5\nseeds {55..68, 79..93}</title>"));
+
+        let odd = "seeds: 1 1\n\nseed-to-<b>&co map:\n5 1 1\n1 5 1\n\n<b>&co-to-location map:\n1 5 1\n5 1 1";
+        let lines: Vec<String> = odd.lines().map(String::from).collect();
+        let svg = Day05::parse(&lines).unwrap().to_svg();
+        assert!(svg.contains(">&lt;b&gt;&amp;co</text>"), "{svg}");
+    }
+}
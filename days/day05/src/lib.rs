@@ -0,0 +1,1597 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt,
+    hint::black_box,
+};
+
+use common::{BenchPhase, ParseError, Solution};
+use intervals::{Interval, IntervalSet};
+use serde::Serialize;
+
+pub mod brute;
+pub mod generate;
+mod graph;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Almanac;
+    type Answer = u64;
+    type Error = AlmanacError;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {
+        parse_almanac(lines.iter().map(|s| s.as_str()))
+    }
+
+    fn part1(almanac: &Self::Input) -> u64 {
+        let seed_to_location = almanac.fold();
+        let locations = almanac.seeds.iter().map(|s| seed_to_location.lookup(*s));
+        locations.min().unwrap()
+    }
+
+    // the input seeds list is not a list of seeds, but a list of ranges
+    //
+    // if the ranges were materialized to a list of seeds, what's the lowest loc number now?
+    fn part2(almanac: &Self::Input) -> u64 {
+        // `brute::run` checks this the slow way
+        pt2_ranges(almanac)
+    }
+
+    fn bench_phases() -> Vec<BenchPhase<Self::Input>> {
+        vec![("lookups", |almanac| {
+            black_box(lookups(almanac));
+        })]
+    }
+}
+
+/// seeds `lookups` sends through the maps
+const LOOKUPS: u64 = 10_000;
+
+/// one map at a time, `LOOKUPS` seeds spread over the seed ranges to their
+/// locations and back again, for timing single lookups. returns how many made
+/// it back
+fn lookups(almanac: &Almanac) -> usize {
+    let (Some(low), Some(high)) = (almanac.seed_ranges.min(), almanac.seed_ranges.iter().last())
+    else {
+        return 0;
+    };
+    let step = ((high.end() - low) / LOOKUPS).max(1);
+    (0..LOOKUPS)
+        .map(|i| low + i * step)
+        .filter(|seed| {
+            let location = almanac
+                .seed_to_location_maps()
+                .fold(*seed, |n, map| map.source_to_dest(&n));
+            almanac.pt2_contains_location(&location) == almanac.pt2_contains_seed(seed)
+        })
+        .count()
+}
+
+/// push whole seed ranges through the maps instead of single seeds. each map
+/// splits a range wherever it crosses the edge of a mapping line, so the
+/// number of ranges stays small no matter how many seeds they cover, and the
+/// lowest location is just the lowest start at the end.
+///
+/// this replaced walking locations upwards from 0 and reversing each one back
+/// to a seed, which needed a hard-coded upper bound and took minutes.
+/// pt2: 44642725  "thats too high"
+fn pt2_ranges(almanac: &Almanac) -> u64 {
+    almanac
+        .locations_of(&almanac.seed_ranges)
+        .min()
+        .expect("parsing makes sure there's a seed range")
+}
+
+/// every seed in the seed ranges, in order
+struct SeedList {
+    seed_ranges: Vec<Interval>,
+    // current range the iterator is sourcing from
+    range_position: usize,
+    // next seed to hand out from that range
+    next_seed: u64,
+}
+
+impl SeedList {
+    fn new(seed_ranges: &IntervalSet) -> Self {
+        let seed_ranges: Vec<_> = seed_ranges.iter().copied().collect();
+        let next_seed = seed_ranges.first().map_or(0, |r| r.start());
+        Self {
+            seed_ranges,
+            range_position: 0,
+            next_seed,
+        }
+    }
+
+    /// how many seeds are left to hand out
+    fn remaining(&self) -> u64 {
+        let Some(current) = self.seed_ranges.get(self.range_position) else {
+            return 0;
+        };
+        let rest: u64 = self.seed_ranges[self.range_position + 1..]
+            .iter()
+            .map(Interval::len)
+            .sum();
+        current.end().saturating_sub(self.next_seed) + rest
+    }
+}
+
+impl Iterator for SeedList {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.seed_ranges.get(self.range_position)?;
+        if range.contains(&self.next_seed) {
+            let seed = self.next_seed;
+            self.next_seed += 1;
+            return Some(seed);
+        }
+        self.range_position += 1;
+        self.next_seed = self.seed_ranges.get(self.range_position)?.start();
+        self.next()
+    }
+}
+
+/// what the almanac parser was looking for when it gave up
+#[derive(Debug, PartialEq)]
+pub enum AlmanacErrorKind {
+    Seeds,
+    /// pt2 would have no seeds at all
+    NoSeedRanges,
+    /// the last seed has no length to go with it
+    UnpairedSeed,
+    Header,
+    Number,
+    MappingLine,
+    /// a second map between the same two categories
+    DuplicateMap,
+    /// maps that lead round in a loop, as the categories on it
+    Cycle(Vec<String>),
+    /// a map out of a category seeds never get to
+    Unreachable(String),
+    /// seeds get as far as this category but no map leads on to `location`
+    MissingLink(String),
+    /// `start + length` is past `u64::MAX`
+    Overflow,
+}
+
+impl fmt::Display for AlmanacErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacErrorKind::Seeds => write!(f, "`seeds: ` followed by numbers"),
+            AlmanacErrorKind::NoSeedRanges => {
+                write!(f, "at least one `start length` pair with seeds in it")
+            }
+            AlmanacErrorKind::UnpairedSeed => write!(
+                f,
+                "a length after this seed, part 2 reads seeds as `start length` pairs"
+            ),
+            AlmanacErrorKind::Header => write!(f, "`<source>-to-<destination> map:`"),
+            AlmanacErrorKind::Number => write!(f, "a number"),
+            AlmanacErrorKind::MappingLine => {
+                write!(f, "three numbers: `<destination> <source> <length>`")
+            }
+            AlmanacErrorKind::DuplicateMap => {
+                write!(
+                    f,
+                    "one map per pair of categories, this pair already has one"
+                )
+            }
+            AlmanacErrorKind::Cycle(categories) => write!(
+                f,
+                "maps that don't loop back on themselves, `{}` does",
+                categories.join(" -> ")
+            ),
+            AlmanacErrorKind::Unreachable(category) => write!(
+                f,
+                "a map out of a category `{SEED}` leads to, nothing leads to `{category}`"
+            ),
+            AlmanacErrorKind::MissingLink(category) => write!(
+                f,
+                "a `{category}-to-...` map, nothing leads on from `{category}` to `{LOCATION}`"
+            ),
+            AlmanacErrorKind::Overflow => {
+                write!(f, "ranges that end by {}, this one runs past", u64::MAX)
+            }
+        }
+    }
+}
+
+pub type AlmanacError = ParseError<AlmanacErrorKind>;
+
+/// something `check` noticed about a map's lines. none of it stops the
+/// almanac being solved, see `AlmanacMap` for how overlaps are settled
+#[derive(Debug, PartialEq)]
+pub enum FindingKind {
+    /// the source range shares values with the one on this earlier line
+    SourceOverlap(usize),
+    /// the destination range shares values with the one on this earlier line
+    DestinationOverlap(usize),
+    /// the destination range lands on values that no line maps, which
+    /// already pass through as themselves
+    PassThroughOverlap(Interval),
+    /// values between this line's source range and the next one's that no
+    /// line maps
+    Gap(Gap),
+}
+
+impl FindingKind {
+    /// how `render_finding` labels it. gaps are normal, the rest less so
+    pub fn severity(&self) -> &'static str {
+        match self {
+            FindingKind::Gap(_) => "note",
+            _ => "warning",
+        }
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindingKind::SourceOverlap(line) => write!(
+                f,
+                "source range overlaps line {}'s, which comes first so maps the values they share",
+                line + 1
+            ),
+            FindingKind::DestinationOverlap(line) => write!(
+                f,
+                "destination range overlaps line {}'s, so some values can't be traced back to one source",
+                line + 1
+            ),
+            FindingKind::PassThroughOverlap(values) => write!(
+                f,
+                "destination range lands on {values:?}, which no line maps, so those values also pass through as themselves"
+            ),
+            FindingKind::Gap(gap) => write!(f, "{gap}"),
+        }
+    }
+}
+
+/// a `FindingKind` and the line it's about
+pub type Finding = ParseError<FindingKind>;
+
+/// `finding` drawn over the almanac line it's about, like a parse error
+pub fn render_finding(finding: &Finding, lines: &[String]) -> String {
+    let heading = format!("{}: {}", finding.kind.severity(), finding.kind);
+    common::annotate(&heading, finding.line, finding.columns.clone(), lines)
+}
+
+/// space separated numbers, erroring with the span of the first one that isn't
+fn parse_numbers(line: &str) -> Result<Vec<u64>, AlmanacError> {
+    let mut nums = vec![];
+    let mut start = 0;
+    for n in line.split(' ') {
+        let num = n
+            .parse::<u64>()
+            .map_err(|_| AlmanacError::new(AlmanacErrorKind::Number, start..start + n.len()))?;
+        nums.push(num);
+        start += n.len() + 1;
+    }
+    Ok(nums)
+}
+
+fn parse_almanac<'a>(s: impl Iterator<Item = &'a str>) -> Result<Almanac, AlmanacError> {
+    // seeds: 79 14 55 13
+    //
+    // seed-to-soil map:
+    // 50 98 2
+    // 52 50 48
+    let mut s = s.enumerate();
+    let first = s.next().map(|(_, line)| line).unwrap_or("");
+    let seeds_raw = first
+        .strip_prefix("seeds: ")
+        .ok_or(AlmanacError::new(AlmanacErrorKind::Seeds, 0..first.len()))?;
+    let seeds = parse_numbers(seeds_raw).map_err(|e| e.offset(0, "seeds: ".len()))?;
+    let seed_ranges = seed_ranges(&seeds, seeds_raw)?;
+
+    let mut tmp = vec![];
+    let mut tmp_start = 0;
+    let mut maps = vec![];
+
+    for (i, line) in s {
+        if line.is_empty() {
+            if !tmp.is_empty() {
+                maps.push(parse_map(&tmp, tmp_start)?);
+                tmp = vec![];
+            }
+        } else {
+            if tmp.is_empty() {
+                tmp_start = i;
+            }
+            tmp.push(line);
+        }
+    }
+    if !tmp.is_empty() {
+        maps.push(parse_map(&tmp, tmp_start)?);
+    }
+    Almanac::new(seeds, seed_ranges, maps)
+}
+
+/// pt2's reading of the seeds line, as `start length` pairs. `raw` is the
+/// line after `seeds: `, for errors
+fn seed_ranges(seeds: &[u64], raw: &str) -> Result<IntervalSet, AlmanacError> {
+    // where each seed is on the line
+    let mut spans = vec![];
+    let mut start = "seeds: ".len();
+    for n in raw.split(' ') {
+        spans.push(start..start + n.len());
+        start += n.len() + 1;
+    }
+    if seeds.len() % 2 == 1 {
+        let last = spans[seeds.len() - 1].clone();
+        return Err(AlmanacError::new(AlmanacErrorKind::UnpairedSeed, last));
+    }
+    let mut ranges = vec![];
+    for (pair, span) in seeds.chunks_exact(2).zip(spans.chunks_exact(2)) {
+        if pair[0].checked_add(pair[1]).is_none() {
+            let columns = span[0].start..span[1].end;
+            return Err(AlmanacError::new(AlmanacErrorKind::Overflow, columns));
+        }
+        ranges.push(Interval::from_len(pair[0], pair[1]));
+    }
+    let ranges = IntervalSet::from_iter(ranges);
+    if ranges.is_empty() {
+        let kind = AlmanacErrorKind::NoSeedRanges;
+        return Err(AlmanacError::new(kind, 0..raw.len()).offset(0, "seeds: ".len()));
+    }
+    Ok(ranges)
+}
+
+fn parse_map(block: &Vec<&str>, line: usize) -> Result<AlmanacMap, AlmanacError> {
+    let mut map = AlmanacMap::try_from(block).map_err(|e| e.offset(line, 0))?;
+    map.line = line;
+    Ok(map)
+}
+
+/// where every almanac starts
+pub const SEED: &str = "seed";
+/// where part 1 and part 2 want to get to
+pub const LOCATION: &str = "location";
+
+/// why `Almanac::map_between` couldn't get from one category to another
+#[derive(Debug, PartialEq)]
+pub enum QueryError {
+    UnknownCategory(String),
+    NoPath {
+        from: String,
+        to: String,
+    },
+    /// the only path runs the other way, and it can't be undone because it
+    /// sends different values to the same place
+    NoInverse {
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::UnknownCategory(category) => {
+                write!(f, "no `{category}` category in the almanac")
+            }
+            QueryError::NoPath { from, to } => {
+                write!(f, "no maps lead from `{from}` to `{to}` or back")
+            }
+            QueryError::NoInverse { from, to } => write!(
+                f,
+                "the maps from `{to}` to `{from}` send different values to the same place, so they can't be run backwards"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    // in the order they were listed, which doesn't matter
+    maps: Vec<AlmanacMap>,
+    // category -> indices of the maps out of it
+    by_source: BTreeMap<String, Vec<usize>>,
+    // pt2 reads the seeds line as `start length` pairs
+    seed_ranges: IntervalSet,
+    // indices of the maps from seed to location, found once the links check out
+    to_location: Vec<usize>,
+}
+
+impl Almanac {
+    /// errors if the maps don't link `seed` to `location` without loops or
+    /// spares
+    fn new(
+        seeds: Vec<u64>,
+        seed_ranges: IntervalSet,
+        maps: Vec<AlmanacMap>,
+    ) -> Result<Self, AlmanacError> {
+        let mut by_source: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, map) in maps.iter().enumerate() {
+            by_source
+                .entry(map.source_name.clone())
+                .or_default()
+                .push(i);
+        }
+        let mut almanac = Self {
+            seeds,
+            maps,
+            by_source,
+            seed_ranges,
+            to_location: vec![],
+        };
+        almanac.check_links()?;
+        almanac.to_location = almanac.path(SEED, LOCATION).expect("just checked");
+        Ok(almanac)
+    }
+
+    /// the maps out of `category`
+    fn maps_from<'a>(&'a self, category: &str) -> impl Iterator<Item = usize> + 'a {
+        self.by_source.get(category).into_iter().flatten().copied()
+    }
+
+    fn has_category(&self, category: &str) -> bool {
+        category == SEED
+            || self
+                .maps
+                .iter()
+                .any(|m| m.source_name == category || m.destination_name == category)
+    }
+
+    /// indices of the maps that lead from `from` to `to`, fewest maps first
+    fn path(&self, from: &str, to: &str) -> Option<Vec<usize>> {
+        // breadth first, remembering which map first got us to each category
+        let mut came_by: HashMap<&str, usize> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        let mut seen = HashSet::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path = vec![];
+                let mut at = to;
+                while at != from {
+                    let i = came_by[at];
+                    path.push(i);
+                    at = &self.maps[i].source_name;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for i in self.maps_from(category) {
+                let next = self.maps[i].destination_name.as_str();
+                if seen.insert(next) {
+                    came_by.insert(next, i);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// the maps from `seed` to `location`, in the order to apply them
+    fn seed_to_location_maps(&self) -> impl DoubleEndedIterator<Item = &AlmanacMap> {
+        self.to_location.iter().map(|&i| &self.maps[i])
+    }
+
+    /// a loop somewhere after `category`, as the categories on it and the map
+    /// that closes it
+    fn find_cycle<'a>(
+        &'a self,
+        category: &'a str,
+        trail: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<(Vec<&'a str>, usize)> {
+        if done.contains(category) {
+            return None;
+        }
+        trail.push(category);
+        for i in self.maps_from(category) {
+            let next = self.maps[i].destination_name.as_str();
+            if let Some(start) = trail.iter().position(|c| *c == next) {
+                let mut cycle = trail[start..].to_vec();
+                cycle.push(next);
+                return Some((cycle, i));
+            }
+            if let Some(found) = self.find_cycle(next, trail, done) {
+                return Some(found);
+            }
+        }
+        trail.pop();
+        done.insert(category);
+        None
+    }
+
+    fn check_links(&self) -> Result<(), AlmanacError> {
+        for (i, map) in self.maps.iter().enumerate() {
+            let same = |m: &AlmanacMap| {
+                m.source_name == map.source_name && m.destination_name == map.destination_name
+            };
+            if self.maps[..i].iter().any(same) {
+                return Err(map.error(AlmanacErrorKind::DuplicateMap));
+            }
+        }
+
+        if let Some((cycle, i)) = self.find_cycle(SEED, &mut vec![], &mut HashSet::new()) {
+            let cycle = cycle.into_iter().map(String::from).collect();
+            return Err(self.maps[i].error(AlmanacErrorKind::Cycle(cycle)));
+        }
+
+        // everything seeds can get to, and the map that first got them there
+        let mut came_by: Vec<(&str, Option<usize>)> = vec![(SEED, None)];
+        let mut next = 0;
+        while let Some(&(category, _)) = came_by.get(next) {
+            for i in self.maps_from(category) {
+                let to = self.maps[i].destination_name.as_str();
+                if came_by.iter().all(|(c, _)| *c != to) {
+                    came_by.push((to, Some(i)));
+                }
+            }
+            next += 1;
+        }
+        let reachable = |category: &str| came_by.iter().any(|(c, _)| *c == category);
+        if let Some(spare) = self.maps.iter().find(|m| !reachable(&m.source_name)) {
+            let kind = AlmanacErrorKind::Unreachable(spare.source_name.clone());
+            return Err(spare.error(kind));
+        }
+        if !reachable(LOCATION) {
+            // the first dead end seeds run into, pointing at the map into it
+            let (dead_end, into) = came_by
+                .iter()
+                .find(|(c, _)| self.maps_from(c).next().is_none())
+                .expect("no loops, so something is a dead end");
+            let kind = AlmanacErrorKind::MissingLink(dead_end.to_string());
+            return Err(match into {
+                Some(i) => self.maps[*i].error(kind),
+                None => AlmanacError::new(kind, 0..0),
+            });
+        }
+        Ok(())
+    }
+
+    /// one map at a time, for checking `fold` against
+    #[cfg(test)]
+    fn seed_to_location(&self, seed: &u64) -> u64 {
+        self.seed_to_location_maps()
+            .fold(*seed, |n, map| map.source_to_dest(&n))
+    }
+
+    /// everything `AlmanacMap::check` finds in every map, in line order
+    pub fn check(&self) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = self.maps.iter().flat_map(AlmanacMap::check).collect();
+        findings.sort_by_key(|f| f.line);
+        findings
+    }
+
+    /// how `seed` gets to its location, one map at a time
+    pub fn explain(&self, seed: u64) -> Explanation {
+        let mut value = seed;
+        let mut steps = vec![];
+        for map in self.seed_to_location_maps() {
+            let matched = map.matching_line(value);
+            let result = map.source_to_dest(&value);
+            steps.push(Step {
+                from: map.source_name.clone(),
+                to: map.destination_name.clone(),
+                value,
+                line: matched.map(|(i, _)| map.line_of(i) + 1),
+                matched: matched.map(|(_, line)| line.to_string()),
+                result,
+            });
+            value = result;
+        }
+        Explanation {
+            seed,
+            location: value,
+            steps,
+        }
+    }
+
+    /// every map chained into one seed -> location map
+    pub fn fold(&self) -> AlmanacMap {
+        self.map_between(SEED, LOCATION)
+            .expect("checked when parsing")
+    }
+
+    /// one map from any category to any other. if the maps only lead from
+    /// `to` to `from`, that's run backwards
+    pub fn map_between(&self, from: &str, to: &str) -> Result<AlmanacMap, QueryError> {
+        if let Some(unknown) = [from, to].into_iter().find(|c| !self.has_category(c)) {
+            return Err(QueryError::UnknownCategory(unknown.to_string()));
+        }
+        let compose = |from: &str, path: Vec<usize>| {
+            let identity = AlmanacMap::identity(from);
+            path.into_iter()
+                .fold(identity, |folded, i| folded.compose(&self.maps[i]))
+        };
+        if let Some(path) = self.path(from, to) {
+            return Ok(compose(from, path));
+        }
+        let (from, to) = (from.to_string(), to.to_string());
+        match self.path(&to, &from) {
+            Some(path) => compose(&to, path)
+                .inverse()
+                .ok_or(QueryError::NoInverse { from, to }),
+            None => Err(QueryError::NoPath { from, to }),
+        }
+    }
+
+    /// `n` in category `from` as category `to`
+    pub fn convert(&self, from: &str, to: &str, n: u64) -> Result<u64, QueryError> {
+        Ok(self.map_between(from, to)?.lookup(n))
+    }
+
+    /// every location the seeds in `seeds` end up at
+    fn locations_of(&self, seeds: &IntervalSet) -> IntervalSet {
+        self.seed_to_location_maps()
+            .fold(seeds.clone(), |ranges, map| map.map_set(&ranges))
+    }
+
+    /// pt 1 would be a Vec::contains, but pt2 changed
+    /// the rules such that a the seed list denotes ranges of seeds
+    fn pt2_contains_seed(&self, seed: &u64) -> bool {
+        self.seed_ranges.contains(seed)
+    }
+
+    /// instead of following the instructions of seed -> soil -> ... -> location
+    /// do the lookup in reverse and see if the almanac has that corresponding seed
+    fn pt2_contains_location(&self, location: &u64) -> bool {
+        let seed = self
+            .seed_to_location_maps()
+            .rev()
+            .fold(*location, |n, map| map.dest_to_source(&n));
+        self.pt2_contains_seed(&seed)
+    }
+}
+
+/// one `<source>-to-<destination> map:` block.
+///
+/// values no line covers pass through as themselves. where two lines' source
+/// ranges overlap, the one listed first maps the values they share, and run
+/// backwards (`dest_to_source`) the first line whose destination range holds
+/// a value takes it back. a map only truly runs backwards (`inverse`) if
+/// every value came from one place, which overlapping destinations, or
+/// destinations landing on values that pass through, get in the way of.
+/// `check` reports all of those.
+#[derive(Clone, Debug)]
+pub struct AlmanacMap {
+    source_name: String,
+    destination_name: String,
+    mapping: Vec<MappingLine>,
+    // the source values each line gets to map, as `(values, index into
+    // mapping)` in order of where the values start, for binary searching
+    by_source: Vec<(Interval, usize)>,
+    // the same for the destination values each line gets to map back
+    by_destination: Vec<(Interval, usize)>,
+    // where the header is in the almanac, for errors
+    line: usize,
+}
+
+#[derive(Clone, Debug)]
+struct MappingLine {
+    destination_start: u64,
+    source_start: u64,
+    length: u64,
+}
+
+impl TryFrom<&Vec<&str>> for AlmanacMap {
+    type Error = AlmanacError;
+
+    /// parse this
+    //
+    // ```
+    // seed-to-soil map:
+    // 50 98 2
+    // 52 50 48
+    // ```
+    //
+    // errors are relative to the header line, the caller knows where the block starts
+    fn try_from(value: &Vec<&str>) -> Result<Self, Self::Error> {
+        let header = value[0];
+        let (source_name, destination_name) = header
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+            .ok_or(AlmanacError::new(AlmanacErrorKind::Header, 0..header.len()))?;
+        let mut mapping = vec![];
+        for (i, line) in value.iter().enumerate().skip(1) {
+            let nums = parse_numbers(line).map_err(|e| e.offset(i, 0))?;
+            let [destination_start, source_start, length] = nums[..] else {
+                return Err(
+                    AlmanacError::new(AlmanacErrorKind::MappingLine, 0..line.len()).offset(i, 0),
+                );
+            };
+            if source_start
+                .max(destination_start)
+                .checked_add(length)
+                .is_none()
+            {
+                return Err(
+                    AlmanacError::new(AlmanacErrorKind::Overflow, 0..line.len()).offset(i, 0)
+                );
+            }
+            mapping.push(MappingLine {
+                destination_start,
+                source_start,
+                length,
+            });
+        }
+        Ok(AlmanacMap::new(source_name, destination_name, mapping))
+    }
+}
+
+/// the map in the almanac's own format
+impl fmt::Display for AlmanacMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-to-{} map:", self.source_name, self.destination_name)?;
+        for line in &self.mapping {
+            write!(f, "\n{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MappingLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_start, self.source_start, self.length
+        )
+    }
+}
+
+/// what `Almanac::explain` found
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Explanation {
+    pub seed: u64,
+    pub location: u64,
+    pub steps: Vec<Step>,
+}
+
+/// one map's part in an `Explanation`
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Step {
+    pub from: String,
+    pub to: String,
+    pub value: u64,
+    /// the almanac line that moved `value`, counting from 1, or `None` if it
+    /// passed through unchanged
+    pub line: Option<usize>,
+    /// that line as written, `<destination> <source> <length>`
+    pub matched: Option<String>,
+    pub result: u64,
+}
+
+/// a table, one row per map
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .steps
+            .iter()
+            .map(|s| s.from.len() + s.to.len() + 4)
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "{:<width$}  {:>12}  {:>5}  {:<32}  {:>12}",
+            "map", "value", "line", "matched", "result"
+        )?;
+        for step in &self.steps {
+            let map = format!("{}-to-{}", step.from, step.to);
+            let line = step.line.map_or("-".to_string(), |l| l.to_string());
+            let matched = step.matched.as_deref().unwrap_or("passed through");
+            writeln!(
+                f,
+                "{map:<width$}  {:>12}  {line:>5}  {matched:<32}  {:>12}",
+                step.value, step.result
+            )?;
+        }
+        write!(f, "seed {} is at location {}", self.seed, self.location)
+    }
+}
+
+/// values between two lines' source ranges that pass through unchanged
+#[derive(Debug, PartialEq)]
+pub struct Gap {
+    /// the line whose source range ends where the gap starts
+    pub after: usize,
+    /// the line whose source range starts where the gap ends
+    pub before: usize,
+    pub values: Interval,
+}
+
+impl fmt::Display for Gap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} between lines {} and {} pass through unchanged",
+            self.values,
+            self.after + 1,
+            self.before + 1
+        )
+    }
+}
+
+impl MappingLine {
+    fn source(&self) -> Interval {
+        Interval::from_len(self.source_start, self.length)
+    }
+
+    fn destination(&self) -> Interval {
+        Interval::from_len(self.destination_start, self.length)
+    }
+}
+
+impl AlmanacMap {
+    fn new(source_name: &str, destination_name: &str, mapping: Vec<MappingLine>) -> Self {
+        Self {
+            source_name: source_name.to_string(),
+            destination_name: destination_name.to_string(),
+            by_source: claims(&mapping, MappingLine::source),
+            by_destination: claims(&mapping, MappingLine::destination),
+            mapping,
+            line: 0,
+        }
+    }
+
+    /// where the `i`th mapping line is in the almanac
+    fn line_of(&self, i: usize) -> usize {
+        self.line + 1 + i
+    }
+
+    /// the overlaps and gaps between the lines of this map, see above, in
+    /// line order. `start + length` overflowing is caught while parsing
+    pub fn check(&self) -> Vec<Finding> {
+        let finding = |i: usize, kind| {
+            let width = self.mapping[i].to_string().len();
+            Finding::new(kind, 0..width).offset(self.line_of(i), 0)
+        };
+        let mut findings = vec![];
+
+        let by_source_start = sorted(&self.mapping, |l| l.source_start);
+        for (first, second) in self.overlapping(&by_source_start, MappingLine::source) {
+            let kind = FindingKind::SourceOverlap(self.line_of(first));
+            findings.push(finding(second, kind));
+        }
+        let by_destination_start = sorted(&self.mapping, |l| l.destination_start);
+        for (first, second) in self.overlapping(&by_destination_start, MappingLine::destination) {
+            let kind = FindingKind::DestinationOverlap(self.line_of(first));
+            findings.push(finding(second, kind));
+        }
+
+        let sources: IntervalSet = self.mapping.iter().map(MappingLine::source).collect();
+        for (i, line) in self.mapping.iter().enumerate() {
+            let passing_through = IntervalSet::from(line.destination()).difference(&sources);
+            if let Some(values) = passing_through.iter().next() {
+                findings.push(finding(i, FindingKind::PassThroughOverlap(*values)));
+            }
+        }
+
+        for gap in self.gaps() {
+            let i = gap.after - self.line - 1;
+            findings.push(finding(i, FindingKind::Gap(gap)));
+        }
+
+        // stable, so a line's findings keep the order above
+        findings.sort_by_key(|f| f.line);
+        findings
+    }
+
+    /// pairs of lines, earlier one first, whose `range`s share values. `order`
+    /// is the lines sorted by the start of `range`
+    fn overlapping(
+        &self,
+        order: &[usize],
+        range: fn(&MappingLine) -> Interval,
+    ) -> Vec<(usize, usize)> {
+        // every earlier range that reaches past where this one starts. the
+        // furthest reaching one is enough to know there's an overlap, but
+        // each pair gets its own finding
+        let mut pairs = vec![];
+        for (n, &i) in order.iter().enumerate() {
+            for &j in &order[..n] {
+                if range(&self.mapping[j])
+                    .intersection(&range(&self.mapping[i]))
+                    .is_some()
+                {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// the stretches between source ranges that no line covers
+    pub fn gaps(&self) -> Vec<Gap> {
+        self.by_source
+            .windows(2)
+            .filter_map(|pair| {
+                let ((a, i), (b, j)) = (pair[0], pair[1]);
+                (a.end() < b.start()).then(|| Gap {
+                    after: self.line_of(i),
+                    before: self.line_of(j),
+                    values: Interval::new(a.end(), b.start()),
+                })
+            })
+            .collect()
+    }
+
+    /// following the rules of the problem
+    ///
+    /// if the input falls within a range covered by the map, return the translated location
+    /// otherwise return the same input
+    fn source_to_dest(&self, n: &u64) -> u64 {
+        match self.matching_line(*n) {
+            Some((_, line)) => n - line.source_start + line.destination_start,
+            None => *n,
+        }
+    }
+
+    /// the source values line `i` gets to map, which is all of its source
+    /// range unless it overlaps an earlier line's
+    pub fn claimed(&self, i: usize) -> IntervalSet {
+        self.by_source
+            .iter()
+            .filter(|(_, j)| *j == i)
+            .map(|(values, _)| *values)
+            .collect()
+    }
+
+    /// the line that maps `n`, and its index. the first listed if more than
+    /// one covers it
+    fn matching_line(&self, n: u64) -> Option<(usize, &MappingLine)> {
+        let i = search(&self.by_source, n)?;
+        Some((i, &self.mapping[i]))
+    }
+
+    /// the seeds (or soil, or...) in `sources` sent through the map. each line
+    /// moves the part of the set its source range covers, and whatever no
+    /// line covers passes through unchanged.
+    fn map_set(&self, sources: &IntervalSet) -> IntervalSet {
+        let mut unmapped = sources.clone();
+        let mut mapped = IntervalSet::new();
+        for line in &self.mapping {
+            let covered = IntervalSet::from(line.source());
+            let moved = unmapped
+                .intersection(&covered)
+                .translate(line.source_start, line.destination_start);
+            mapped = mapped.union(&moved);
+            unmapped = unmapped.difference(&covered);
+        }
+        mapped.union(&unmapped)
+    }
+
+    /// the whole of `0..u64::MAX` as `(source, destination start)` pieces in
+    /// source order, with the gaps between lines as pieces that map to
+    /// themselves
+    fn pieces(&self) -> Vec<(Interval, u64)> {
+        let mut pieces = vec![];
+        let mut at = 0;
+        for &(values, i) in &self.by_source {
+            if at < values.start() {
+                pieces.push((Interval::new(at, values.start()), at));
+            }
+            let line = &self.mapping[i];
+            let moved = values.translate(line.source_start, line.destination_start);
+            pieces.push((values, moved.start()));
+            at = values.end();
+        }
+        if at < u64::MAX {
+            pieces.push((Interval::new(at, u64::MAX), at));
+        }
+        pieces
+    }
+
+    /// a map made of `pieces`, which have to be in source order. the pieces
+    /// that map to themselves are left out, and neighbours that move by the
+    /// same amount become one line
+    fn from_pieces(
+        source_name: &str,
+        destination_name: &str,
+        pieces: impl IntoIterator<Item = (Interval, u64)>,
+    ) -> Self {
+        let mut mapping: Vec<MappingLine> = vec![];
+        for (source, destination_start) in pieces {
+            if source.start() == destination_start {
+                continue;
+            }
+            if let Some(last) = mapping.last_mut() {
+                if last.source().end() == source.start()
+                    && last.destination().end() == destination_start
+                {
+                    last.length += source.len();
+                    continue;
+                }
+            }
+            mapping.push(MappingLine {
+                destination_start,
+                source_start: source.start(),
+                length: source.len(),
+            });
+        }
+        Self::new(source_name, destination_name, mapping)
+    }
+
+    /// sends everything in `category` to itself
+    fn identity(category: &str) -> Self {
+        Self::from_pieces(category, category, [])
+    }
+
+    /// an error pointing at this map's header
+    fn error(&self, kind: AlmanacErrorKind) -> AlmanacError {
+        let header = format!("{}-to-{} map:", self.source_name, self.destination_name);
+        AlmanacError::new(kind, 0..header.len()).offset(self.line, 0)
+    }
+
+    /// this map followed by `next`, as one map. `next` should start from the
+    /// category this one ends at.
+    ///
+    /// the lines come out sorted and without overlaps, so `lookup` works on it
+    pub fn compose(&self, next: &AlmanacMap) -> AlmanacMap {
+        let next_pieces = next.pieces();
+        let mut pieces = vec![];
+        for (source, destination_start) in self.pieces() {
+            let destination = source.translate(source.start(), destination_start);
+            // the pieces of `next` tile everything, so the ones `destination`
+            // runs through are side by side from the first it touches
+            let first = next_pieces.partition_point(|(s, _)| s.end() <= destination.start());
+            for (next_source, next_destination) in &next_pieces[first..] {
+                let Some(overlap) = destination.intersection(next_source) else {
+                    break;
+                };
+                pieces.push((
+                    overlap.translate(destination_start, source.start()),
+                    overlap
+                        .translate(next_source.start(), *next_destination)
+                        .start(),
+                ));
+            }
+        }
+        Self::from_pieces(&self.source_name, &next.destination_name, pieces)
+    }
+
+    /// the map run backwards, or `None` if two parts of it land on the same
+    /// values so there's no single way back. `check` warns about maps like
+    /// that
+    pub fn inverse(&self) -> Option<AlmanacMap> {
+        let mut pieces: Vec<_> = self
+            .pieces()
+            .into_iter()
+            .map(|(source, destination_start)| {
+                let destination = source.translate(source.start(), destination_start);
+                (destination, source.start())
+            })
+            .collect();
+        pieces.sort_by_key(|(destination, _)| destination.start());
+        // the pieces add up to the whole range, so if they don't overlap they tile it
+        if pieces.windows(2).any(|w| w[0].0.end() > w[1].0.start()) {
+            return None;
+        }
+        Some(Self::from_pieces(
+            &self.destination_name,
+            &self.source_name,
+            pieces,
+        ))
+    }
+
+    /// where `n` ends up
+    pub fn lookup(&self, n: u64) -> u64 {
+        self.source_to_dest(&n)
+    }
+
+    /// `source_to_dest` backwards: where the first line whose destination
+    /// range holds `n` takes it
+    fn dest_to_source(&self, n: &u64) -> u64 {
+        match search(&self.by_destination, *n) {
+            Some(i) => n - self.mapping[i].destination_start + self.mapping[i].source_start,
+            None => *n,
+        }
+    }
+}
+
+/// what each line's `range` gets of the values, which is whatever the lines
+/// before it haven't, as `(values, index into mapping)` in order of where the
+/// values start
+fn claims(mapping: &[MappingLine], range: fn(&MappingLine) -> Interval) -> Vec<(Interval, usize)> {
+    let mut unclaimed = IntervalSet::from(Interval::new(0, u64::MAX));
+    let mut claims = vec![];
+    for (i, line) in mapping.iter().enumerate() {
+        let covered = IntervalSet::from(range(line));
+        claims.extend(unclaimed.intersection(&covered).iter().map(|v| (*v, i)));
+        unclaimed = unclaimed.difference(&covered);
+    }
+    claims.sort_by_key(|(values, _)| values.start());
+    claims
+}
+
+/// the line whose claim in `claims` holds `n`, by binary search
+fn search(claims: &[(Interval, usize)], n: u64) -> Option<usize> {
+    let i = claims.partition_point(|(values, _)| values.end() <= n);
+    let &(values, i) = claims.get(i)?;
+    values.contains(&n).then_some(i)
+}
+
+/// indices into `mapping` in order of `start`
+fn sorted(mapping: &[MappingLine], start: fn(&MappingLine) -> u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..mapping.len()).collect();
+    order.sort_by_key(|&i| start(&mapping[i]));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) static EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_ex1() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let m = &almanac.maps[0];
+        assert_eq!(m.source_name, "seed");
+        assert_eq!(m.destination_name, "soil");
+        assert_eq!(m.source_to_dest(&79), 81);
+    }
+
+    #[test]
+    fn pt2_contains() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        assert!(almanac.pt2_contains_seed(&80));
+        assert!(!almanac.pt2_contains_seed(&10));
+    }
+
+    /// a line covers `start..start + length`, so the value at `start + length`
+    /// is past it and passes through
+    #[test]
+    fn test_range_ends_are_exclusive() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        // 50 98 2 and 52 50 48
+        let seed_to_soil = &almanac.maps[0];
+        assert_eq!(seed_to_soil.source_to_dest(&99), 51);
+        assert_eq!(seed_to_soil.source_to_dest(&100), 100);
+        assert_eq!(seed_to_soil.source_to_dest(&97), 99);
+        assert_eq!(seed_to_soil.source_to_dest(&98), 50);
+        assert_eq!(seed_to_soil.dest_to_source(&51), 99);
+        assert_eq!(seed_to_soil.dest_to_source(&52), 50);
+        assert_eq!(seed_to_soil.dest_to_source(&99), 97);
+        assert_eq!(seed_to_soil.dest_to_source(&100), 100);
+        assert_eq!(seed_to_soil.lookup(100), 100);
+    }
+
+    #[test]
+    fn test_dest_to_source_with_overlapping_destinations() {
+        // the first line's destination holds the other two
+        let almanac = one_map("1000 0 100\n1010 500 5\n1020 600 10").unwrap();
+        let map = &almanac.maps[0];
+        assert_eq!(map.dest_to_source(&1050), 50);
+        assert_eq!(map.dest_to_source(&1012), 12);
+        assert_eq!(map.dest_to_source(&1099), 99);
+        assert_eq!(map.dest_to_source(&1100), 1100);
+        assert_eq!(map.dest_to_source(&999), 999);
+
+        // overlapping part way, the earlier line still wins what they share
+        let almanac = one_map("60 0 10\n50 20 15").unwrap();
+        let map = &almanac.maps[0];
+        assert_eq!(map.dest_to_source(&62), 2);
+        assert_eq!(map.dest_to_source(&55), 25);
+        assert_eq!(map.dest_to_source(&64), 4);
+        for n in 0..200 {
+            let back = map
+                .mapping
+                .iter()
+                .find(|l| l.destination().contains(&n))
+                .map_or(n, |l| n - l.destination_start + l.source_start);
+            assert_eq!(map.dest_to_source(&n), back, "{n}");
+        }
+    }
+
+    #[test]
+    fn pt2_check_if_has_locations() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let loc_map = &almanac.maps[almanac.maps.len() - 1];
+        assert!(almanac.pt2_contains_seed(&79));
+        assert_eq!(loc_map.source_to_dest(&79), 83);
+        assert_eq!(loc_map.dest_to_source(&83), 79);
+        assert!(almanac.pt2_contains_location(&83));
+    }
+
+    #[test]
+    fn test_map_set_splits_at_line_edges() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let seed_to_soil = &almanac.maps[0];
+        // 50 98 2 / 52 50 48
+        let seeds = IntervalSet::from(Interval::new(40, 100));
+        // 40..50 stays, 50..98 moves up to 52..100, 98..100 moves down to 50..52
+        assert_eq!(
+            seed_to_soil.map_set(&seeds),
+            IntervalSet::from(Interval::new(40, 100))
+        );
+        let seeds = IntervalSet::from(Interval::new(97, 99));
+        assert_eq!(
+            seed_to_soil.map_set(&seeds),
+            [Interval::new(50, 51), Interval::new(99, 100)]
+                .into_iter()
+                .collect()
+        );
+        let seeds = IntervalSet::from(Interval::new(10, 20));
+        assert_eq!(seed_to_soil.map_set(&seeds), seeds);
+    }
+
+    #[test]
+    fn test_seed_ranges_pair_up() {
+        // 79 14 55 13 is 79..93 and 55..68, not 79..93, 14..69 and 55..68
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let seeds: Vec<_> = SeedList::new(&almanac.seed_ranges).collect();
+        assert_eq!(seeds.len(), 14 + 13);
+        assert_eq!(seeds[0], 55);
+        assert_eq!(seeds[12], 67);
+        assert_eq!(seeds[13], 79);
+        assert_eq!(seeds[26], 92);
+    }
+
+    #[test]
+    fn test_pt2_example() {
+        let lines: Vec<String> = EXAMPLE.lines().map(String::from).collect();
+        assert_eq!(Day05::solve_part2(&lines), Ok(46));
+    }
+
+    #[test]
+    fn test_compose() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let [seed_to_soil, soil_to_fertilizer, ..] = &almanac.maps[..] else {
+            panic!()
+        };
+        let seed_to_fertilizer = seed_to_soil.compose(soil_to_fertilizer);
+        assert_eq!(seed_to_fertilizer.source_name, "seed");
+        assert_eq!(seed_to_fertilizer.destination_name, "fertilizer");
+        for n in 0..120 {
+            let soil = seed_to_soil.source_to_dest(&n);
+            assert_eq!(
+                seed_to_fertilizer.lookup(n),
+                soil_to_fertilizer.source_to_dest(&soil),
+                "seed {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fold_and_inverse() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let seed_to_location = almanac.fold();
+        let location_to_seed = seed_to_location.inverse().unwrap();
+        assert_eq!(location_to_seed.source_name, "location");
+        for n in 0..120 {
+            let location = seed_to_location.lookup(n);
+            assert_eq!(location, almanac.seed_to_location(&n), "seed {n}");
+            assert_eq!(location_to_seed.lookup(location), n, "seed {n}");
+        }
+        let locations: Vec<_> = [79, 14, 55, 13].map(|s| seed_to_location.lookup(s)).into();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+    }
+
+    #[test]
+    fn test_folded_map_dumps_as_almanac() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let folded = almanac.fold();
+        let dumped = format!("seeds: 79 1\n\n{folded}");
+        assert!(dumped.contains("seed-to-location map:\n"), "{dumped}");
+        let reparsed = parse_almanac(dumped.split("\n")).unwrap();
+        assert_eq!(reparsed.maps.len(), 1);
+        for n in 0..120 {
+            assert_eq!(reparsed.maps[0].source_to_dest(&n), folded.lookup(n));
+        }
+    }
+
+    #[test]
+    fn test_inverse_needs_one_way_back() {
+        // parsing won't allow this, 0..5 passes through and 10..15 lands on it
+        let map = AlmanacMap::new(
+            SEED,
+            LOCATION,
+            vec![MappingLine {
+                destination_start: 0,
+                source_start: 10,
+                length: 5,
+            }],
+        );
+        assert!(map.inverse().is_none());
+    }
+
+    /// the example with its maps listed in a different order
+    fn shuffled_example() -> String {
+        let mut blocks: Vec<&str> = EXAMPLE.split("\n\n").collect();
+        blocks[1..].reverse();
+        blocks.swap(2, 5);
+        blocks.join("\n\n")
+    }
+
+    #[test]
+    fn test_maps_in_any_order() {
+        let lines: Vec<String> = shuffled_example().lines().map(String::from).collect();
+        assert!(!lines[2].starts_with("seed-to-soil"));
+        assert_eq!(Day05::solve_part1(&lines), Ok(35));
+        assert_eq!(Day05::solve_part2(&lines), Ok(46));
+    }
+
+    #[test]
+    fn test_queries_between_categories() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let maps = &almanac.maps;
+        for n in 0..120 {
+            // soil -> fertilizer -> water -> light -> temperature -> humidity
+            let humidity = maps[1..6].iter().fold(n, |n, m| m.source_to_dest(&n));
+            assert_eq!(almanac.convert("soil", "humidity", n), Ok(humidity));
+            assert_eq!(almanac.convert("humidity", "soil", humidity), Ok(n));
+        }
+        assert_eq!(almanac.convert("water", "water", 7), Ok(7));
+        assert_eq!(
+            almanac.convert("soil", "dirt", 1),
+            Err(QueryError::UnknownCategory("dirt".to_string()))
+        );
+
+        let forked =
+            "seeds: 1 1\n\nseed-to-soil map:\n1 0 1\n0 1 1\n\nseed-to-location map:\n5 0 1\n0 5 1";
+        let almanac = parse_almanac(forked.split("\n")).unwrap();
+        assert_eq!(almanac.convert(SEED, LOCATION, 0), Ok(5));
+        assert_eq!(
+            almanac.convert("soil", LOCATION, 0),
+            Err(QueryError::NoPath {
+                from: "soil".to_string(),
+                to: LOCATION.to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_link_errors() {
+        let twice = format!("{EXAMPLE}\n\nwater-to-light map:\n1 2 1\n2 1 1");
+        let e = parse_almanac(twice.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::DuplicateMap, 0..19).offset(34, 0)
+        );
+
+        let looped = format!("{EXAMPLE}\n\nlight-to-soil map:\n1 2 1\n2 1 1");
+        let e = parse_almanac(looped.split("\n")).err().unwrap();
+        let cycle = ["soil", "fertilizer", "water", "light", "soil"];
+        assert_eq!(
+            e,
+            AlmanacError::new(
+                AlmanacErrorKind::Cycle(cycle.map(String::from).into()),
+                0..18
+            )
+            .offset(34, 0)
+        );
+
+        let spare = format!("{EXAMPLE}\n\nsand-to-water map:\n1 2 1\n2 1 1");
+        let e = parse_almanac(spare.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::Unreachable("sand".to_string()), 0..18)
+                .offset(34, 0)
+        );
+
+        let (short, _) = EXAMPLE.split_once("\n\nhumidity-to-location").unwrap();
+        let e = parse_almanac(short.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::MissingLink("humidity".to_string()), 0..28)
+                .offset(26, 0)
+        );
+        assert_eq!(
+            parse_almanac(["seeds: 1 1"].into_iter()).err().unwrap(),
+            AlmanacError::new(AlmanacErrorKind::MissingLink(SEED.to_string()), 0..0)
+        );
+    }
+
+    /// `lines` as the only map in an almanac, header on line 3
+    fn one_map(lines: &str) -> Result<Almanac, AlmanacError> {
+        let almanac = format!("seeds: 1 1\n\nseed-to-location map:\n{lines}");
+        parse_almanac(almanac.split("\n"))
+    }
+
+    #[test]
+    fn test_overlap_findings() {
+        // line 4's source overlaps line 3's, which wins the values they share
+        let almanac = one_map("50 98 2\n10 0 99").unwrap();
+        let map = &almanac.maps[0];
+        let finding = |kind| Finding::new(kind, 0..7).offset(4, 0);
+        assert_eq!(
+            map.check(),
+            vec![
+                finding(FindingKind::SourceOverlap(3)),
+                finding(FindingKind::DestinationOverlap(3)),
+                finding(FindingKind::PassThroughOverlap(Interval::new(100, 109))),
+            ]
+        );
+        assert_eq!(map.lookup(98), 50);
+        assert_eq!(map.lookup(97), 107);
+        assert_eq!(map.lookup(99), 51);
+        assert_eq!(map.lookup(100), 100);
+        assert_eq!(almanac.check(), map.check());
+
+        let almanac = one_map("50 98 2\n52 50 48\n51 10 5").unwrap();
+        assert_eq!(
+            almanac.check(),
+            vec![
+                Finding::new(FindingKind::DestinationOverlap(3), 0..7).offset(5, 0),
+                Finding::new(FindingKind::DestinationOverlap(4), 0..7).offset(5, 0),
+                Finding::new(
+                    FindingKind::Gap(Gap {
+                        after: 5,
+                        before: 4,
+                        values: Interval::new(15, 50)
+                    }),
+                    0..7
+                )
+                .offset(5, 0),
+            ]
+        );
+
+        // 0..5 isn't any line's source, so those values already go to 0..5
+        let almanac = one_map("0 10 5").unwrap();
+        assert_eq!(
+            almanac.check(),
+            vec![
+                Finding::new(FindingKind::PassThroughOverlap(Interval::new(0, 5)), 0..6)
+                    .offset(3, 0)
+            ]
+        );
+        assert_eq!(
+            render_finding(
+                &almanac.check()[0],
+                &[
+                    "seeds: 1 1".to_string(),
+                    String::new(),
+                    "seed-to-location map:".to_string(),
+                    "0 10 5".to_string(),
+                ]
+            ),
+            "warning: destination range lands on 0..5, which no line maps, so those \
+             values also pass through as themselves\n \
+             --> line 4, column 1\n  |\n4 | 0 10 5\n  | ^^^^^^"
+        );
+
+        let e = one_map("1 18446744073709551615 2").err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::Overflow, 0..24).offset(3, 0)
+        );
+        // swapping two ranges is fine
+        assert!(one_map("10 20 10\n20 10 10").unwrap().check().is_empty());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let almanac = one_map("20 10 10\n10 20 10\n40 30 5\n30 40 5").unwrap();
+        let gaps = almanac.maps[0].gaps();
+        assert_eq!(
+            gaps,
+            vec![Gap {
+                after: 5,
+                before: 6,
+                values: Interval::new(35, 40)
+            }]
+        );
+        assert_eq!(
+            gaps[0].to_string(),
+            "35..40 between lines 6 and 7 pass through unchanged"
+        );
+    }
+
+    #[test]
+    fn test_lookups_agree_both_ways() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        for map in &almanac.maps {
+            assert!(map.check().iter().all(|f| f.kind.severity() == "note"));
+            for n in 0..120 {
+                assert_eq!(map.dest_to_source(&map.source_to_dest(&n)), n, "{map}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let almanac = parse_almanac(EXAMPLE.split("\n")).unwrap();
+        let explained = almanac.explain(79);
+        assert_eq!(explained.location, 82);
+        assert_eq!(explained.steps.len(), 7);
+        assert_eq!(
+            explained.steps[0],
+            Step {
+                from: "seed".to_string(),
+                to: "soil".to_string(),
+                value: 79,
+                line: Some(5),
+                matched: Some("52 50 48".to_string()),
+                result: 81,
+            }
+        );
+        // fertilizer 81 isn't in any fertilizer-to-water line
+        assert_eq!(explained.steps[2].line, None);
+        assert_eq!(explained.steps[2].result, 81);
+        let results: Vec<_> = explained.steps.iter().map(|s| s.result).collect();
+        assert_eq!(results, vec![81, 81, 81, 74, 78, 78, 82]);
+
+        let table = explained.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert!(lines[1].starts_with("seed-to-soil "), "{table}");
+        assert!(lines[3].contains("passed through"), "{table}");
+        assert_eq!(lines[8], "seed 79 is at location 82");
+
+        let json = serde_json::to_value(&explained).unwrap();
+        assert_eq!(json["location"], 82);
+        assert_eq!(json["steps"][0]["matched"], "52 50 48");
+        assert!(json["steps"][2]["line"].is_null());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let bad_number = EXAMPLE.replace("52 50 48", "52 5o 48");
+        let e = parse_almanac(bad_number.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::Number, 3..5).offset(4, 0)
+        );
+
+        let short_line = EXAMPLE.replace("37 52 2", "37 52");
+        let e = parse_almanac(short_line.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::MappingLine, 0..5).offset(8, 0)
+        );
+
+        // part 1 could go on, but part 2 has nothing to work with
+        let e = parse_almanac(EXAMPLE.replace("79 14 55 13", "7").split("\n"))
+            .err()
+            .unwrap();
+        assert_eq!(e, AlmanacError::new(AlmanacErrorKind::UnpairedSeed, 7..8));
+        let e = parse_almanac(EXAMPLE.replace("79 14 55 13", "79 14 55").split("\n"))
+            .err()
+            .unwrap();
+        assert_eq!(e, AlmanacError::new(AlmanacErrorKind::UnpairedSeed, 13..15));
+        let max = EXAMPLE.replace("79 14 55 13", "79 14 18446744073709551615 2");
+        let e = parse_almanac(max.split("\n")).err().unwrap();
+        assert_eq!(e, AlmanacError::new(AlmanacErrorKind::Overflow, 13..35));
+        let e = parse_almanac(EXAMPLE.replace("79 14 55 13", "79 0").split("\n"))
+            .err()
+            .unwrap();
+        assert_eq!(e, AlmanacError::new(AlmanacErrorKind::NoSeedRanges, 7..11));
+
+        let bad_header = EXAMPLE.replace("water-to-light map:", "water to light map:");
+        let e = parse_almanac(bad_header.split("\n")).err().unwrap();
+        assert_eq!(
+            e,
+            AlmanacError::new(AlmanacErrorKind::Header, 0..19).offset(17, 0)
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! half-open intervals and sets of them.
+//!
+//! everything here is `start..end` with `end` left out, so `n` values from
+//! `s` are `s..s + n`, and two intervals touch without overlapping when one
+//! ends where the other starts. no `<=` against an end, no `- 1` anywhere.
+
+use std::fmt;
+
+/// `start..end`, with `start <= end`. `start == end` is empty
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T = u64> {
+    start: T,
+    end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "interval can't end before it starts");
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// the first value past the interval
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value < self.end
+    }
+
+    /// the values in both, if there are any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Self { start, end })
+    }
+
+    /// the values below `at` and the values from `at` on, either of which
+    /// can be missing
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let at = at.clamp(self.start, self.end);
+        let below = Self::new(self.start, at);
+        let above = Self::new(at, self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+impl Interval<u64> {
+    /// `len` values starting at `start`
+    ///
+    /// panics if that runs past `u64::MAX`
+    pub fn from_len(start: u64, len: u64) -> Self {
+        let end = start
+            .checked_add(len)
+            .unwrap_or_else(|| panic!("{start} + {len} overflows u64"));
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// shifted so that `from` would land on `to`, the way a map line moves
+    /// its source range onto its destination range
+    ///
+    /// panics if the result doesn't fit in a `u64`
+    pub fn translate(&self, from: u64, to: u64) -> Self {
+        let shift = |n: u64| {
+            if to >= from {
+                n.checked_add(to - from)
+            } else {
+                n.checked_sub(from - to)
+            }
+            .unwrap_or_else(|| panic!("translating {self:?} from {from} to {to} overflows"))
+        };
+        Self {
+            start: shift(self.start),
+            end: shift(self.end),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+/// a set of values kept as sorted intervals that don't overlap, touch, or
+/// come up empty, so two sets with the same values always compare equal
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T = u64> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sort, drop the empties and merge anything overlapping or touching
+    fn normalized(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = Self::normalized(intervals);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// the intervals in order
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// the smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        // the first interval ending past `value` is the only one that could hold it
+        let i = self.intervals.partition_point(|i| i.end <= *value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.iter().chain(other.iter()).copied().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut out = vec![];
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            out.extend(x.intersection(y));
+            // whichever ends first can't overlap anything further along the other
+            if x.end <= y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { intervals: out }
+    }
+
+    /// the values in `self` that aren't in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = vec![];
+        let mut others = other.iter().peekable();
+        for interval in self.iter() {
+            let mut rest = Some(*interval);
+            // skip what ends before this interval starts, it can't cut later ones either
+            while others.next_if(|o| o.end <= interval.start).is_some() {}
+            for cut in others.clone() {
+                let Some(current) = rest else { break };
+                if cut.start >= current.end {
+                    break;
+                }
+                let (below, _) = current.split_at(cut.start);
+                let (_, above) = current.split_at(cut.end);
+                out.extend(below);
+                rest = above;
+            }
+            out.extend(rest);
+        }
+        Self { intervals: out }
+    }
+
+    /// the values below `at` and the values from `at` on
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (mut below, mut above) = (vec![], vec![]);
+        for interval in self.iter() {
+            let (b, a) = interval.split_at(at);
+            below.extend(b);
+            above.extend(a);
+        }
+        (Self { intervals: below }, Self { intervals: above })
+    }
+}
+
+impl IntervalSet<u64> {
+    /// every interval shifted so `from` would land on `to`, see [`Interval::translate`]
+    pub fn translate(&self, from: u64, to: u64) -> Self {
+        // a uniform shift keeps the order and the gaps
+        Self {
+            intervals: self.iter().map(|i| i.translate(from, to)).collect(),
+        }
+    }
+
+    /// how many values are in the set
+    pub fn len(&self) -> u64 {
+        self.iter().map(|i| i.len()).sum()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+impl<T: Ord + Copy> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalized(vec![interval])
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalizes() {
+        let s = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (20, 20)]);
+        assert_eq!(format!("{s:?}"), "{0..3, 5..10}");
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(&2));
+        assert!(!s.contains(&3));
+        assert!(!s.contains(&10));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+        assert_eq!(
+            a.split_at(25),
+            (set(&[(0, 10), (20, 25)]), set(&[(25, 30)]))
+        );
+        assert_eq!(a.translate(0, 5), set(&[(5, 15), (25, 35)]));
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::from_len(98, 2);
+        assert_eq!(i, Interval::new(98, 100));
+        assert_eq!(i.len(), 2);
+        assert!(i.contains(&99));
+        assert!(!i.contains(&100));
+        assert_eq!(i.translate(98, 50), Interval::new(50, 52));
+        assert_eq!(
+            i.split_at(99),
+            (Some(Interval::new(98, 99)), Some(Interval::new(99, 100)))
+        );
+        assert_eq!(i.split_at(0), (None, Some(i)));
+        assert_eq!(i.intersection(&Interval::new(100, 200)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn test_translate_overflow() {
+        Interval::new(10, 20).translate(0, u64::MAX - 15);
+    }
+
+    // the model the properties check against: every value, spelled out
+    fn members(set: &IntervalSet) -> BTreeSet<u64> {
+        set.iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    fn is_normalized(set: &IntervalSet) -> bool {
+        set.iter().all(|i| !i.is_empty()) && set.intervals.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    fn interval() -> impl Strategy<Value = Interval> {
+        (0..64u64, 0..16u64).prop_map(|(start, len)| Interval::from_len(start, len))
+    }
+
+    fn interval_set() -> impl Strategy<Value = IntervalSet> {
+        prop::collection::vec(interval(), 0..6).prop_map(IntervalSet::from_iter)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_from_iter_keeps_every_value(intervals in prop::collection::vec(interval(), 0..6)) {
+            let set: IntervalSet = intervals.iter().copied().collect();
+            let expected: BTreeSet<u64> = intervals.iter().flat_map(|i| i.start..i.end).collect();
+            prop_assert!(is_normalized(&set));
+            prop_assert_eq!(members(&set), expected);
+            prop_assert_eq!(set.len(), members(&set).len() as u64);
+        }
+
+        #[test]
+        fn prop_contains(a in interval_set(), x in 0..96u64) {
+            prop_assert_eq!(a.contains(&x), members(&a).contains(&x));
+        }
+
+        #[test]
+        fn prop_union(a in interval_set(), b in interval_set()) {
+            let u = a.union(&b);
+            prop_assert!(is_normalized(&u));
+            prop_assert_eq!(members(&u), &members(&a) | &members(&b));
+        }
+
+        #[test]
+        fn prop_intersection(a in interval_set(), b in interval_set()) {
+            let i = a.intersection(&b);
+            prop_assert!(is_normalized(&i));
+            prop_assert_eq!(members(&i), &members(&a) & &members(&b));
+        }
+
+        #[test]
+        fn prop_difference(a in interval_set(), b in interval_set()) {
+            let d = a.difference(&b);
+            prop_assert!(is_normalized(&d));
+            prop_assert_eq!(members(&d), &members(&a) - &members(&b));
+        }
+
+        #[test]
+        fn prop_split_at(a in interval_set(), at in 0..96u64) {
+            let (below, above) = a.split_at(at);
+            prop_assert!(is_normalized(&below) && is_normalized(&above));
+            prop_assert!(members(&below).iter().all(|&x| x < at));
+            prop_assert!(members(&above).iter().all(|&x| x >= at));
+            prop_assert_eq!(below.union(&above), a);
+        }
+
+        #[test]
+        fn prop_translate(a in interval_set(), to in 0..96u64) {
+            let from = a.min().unwrap_or(0);
+            let moved = a.translate(from, to);
+            prop_assert!(is_normalized(&moved));
+            let expected: BTreeSet<u64> = members(&a).iter().map(|x| x - from + to).collect();
+            prop_assert_eq!(members(&moved), expected);
+            prop_assert_eq!(moved.translate(to, from), a);
+        }
+    }
+}
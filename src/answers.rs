@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use serde::Deserialize;
+
+/// what we know about one part's answer for one input
+#[derive(Debug, Default, Deserialize)]
+pub struct Recorded {
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub wrong: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Parts {
+    pub part1: Option<Recorded>,
+    pub part2: Option<Recorded>,
+}
+
+/// the contents of `answers.toml`: `dayNN` -> input name -> parts
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, Parts>>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        raw.parse()
+    }
+
+    /// the input names with recorded answers for a day, or just the default
+    /// input if the day has none yet
+    pub fn inputs(&self, day: u8) -> Vec<String> {
+        match self.0.get(&key(day)) {
+            Some(inputs) if !inputs.is_empty() => inputs.keys().cloned().collect(),
+            _ => vec!["1".to_string()],
+        }
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&Recorded> {
+        let parts = self.0.get(&key(day))?.get(input)?;
+        match part {
+            1 => parts.part1.as_ref(),
+            2 => parts.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answers: Answers = toml::from_str(s).map_err(|e| e.to_string())?;
+        if let Some(bad) = answers.0.keys().find(|k| day_of(k).is_none()) {
+            return Err(format!("'{bad}' is not a day, expected day01 to day25"));
+        }
+        Ok(answers)
+    }
+}
+
+fn key(day: u8) -> String {
+    format!("day{day:02}")
+}
+
+fn day_of(key: &str) -> Option<u8> {
+    key.strip_prefix("day")
+        .filter(|n| n.len() == 2)
+        .and_then(|n| n.parse().ok())
+        .filter(|n| (1..=25).contains(n))
+}
+
+/// how an answer compares to what's recorded
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    /// one of the answers listed under `wrong`
+    KnownWrong {
+        expected: Option<String>,
+    },
+    Mismatch {
+        expected: String,
+    },
+    Unrecorded,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::KnownWrong { .. } | Verdict::Mismatch { .. })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::KnownWrong {
+                expected: Some(expected),
+            } => write!(f, "KNOWN WRONG, expected {expected}"),
+            Verdict::KnownWrong { expected: None } => write!(f, "KNOWN WRONG"),
+            Verdict::Mismatch { expected } => write!(f, "MISMATCH, expected {expected}"),
+            Verdict::Unrecorded => write!(f, "no recorded answer"),
+        }
+    }
+}
+
+pub fn check(recorded: Option<&Recorded>, got: &str) -> Verdict {
+    let Some(recorded) = recorded else {
+        return Verdict::Unrecorded;
+    };
+    if recorded.wrong.iter().any(|w| w == got) {
+        return Verdict::KnownWrong {
+            expected: recorded.answer.clone(),
+        };
+    }
+    match &recorded.answer {
+        Some(answer) if answer == got => Verdict::Correct,
+        Some(answer) => Verdict::Mismatch {
+            expected: answer.clone(),
+        },
+        None => Verdict::Unrecorded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = r#"
+[day05."1"]
+part1 = { answer = "35" }
+part2 = { answer = "46", wrong = ["44642725"] }
+
+[day05.friend]
+part2 = { wrong = ["12"] }
+"#;
+
+    #[test]
+    fn test_checks_against_recorded() {
+        let answers: Answers = EXAMPLE.parse().unwrap();
+        assert_eq!(answers.inputs(5), vec!["1", "friend"]);
+        assert_eq!(answers.inputs(6), vec!["1"]);
+
+        assert_eq!(check(answers.get(5, "1", 1), "35"), Verdict::Correct);
+        assert_eq!(
+            check(answers.get(5, "1", 1), "36"),
+            Verdict::Mismatch {
+                expected: "35".to_string()
+            }
+        );
+        assert_eq!(
+            check(answers.get(5, "1", 2), "44642725"),
+            Verdict::KnownWrong {
+                expected: Some("46".to_string())
+            }
+        );
+        assert_eq!(
+            check(answers.get(5, "friend", 2), "12"),
+            Verdict::KnownWrong { expected: None }
+        );
+        assert_eq!(
+            check(answers.get(5, "friend", 2), "13"),
+            Verdict::Unrecorded
+        );
+        assert_eq!(
+            check(answers.get(5, "friend", 1), "13"),
+            Verdict::Unrecorded
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_days() {
+        assert!("[day5.\"1\"]\npart1 = { answer = \"1\" }"
+            .parse::<Answers>()
+            .is_err());
+        assert!("[day26.\"1\"]\npart1 = { answer = \"1\" }"
+            .parse::<Answers>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_checked_in_answers_parse() {
+        let answers: Answers = include_str!("../answers.toml").parse().unwrap();
+        let day05 = answers.get(5, "1", 2).unwrap();
+        assert!(day05.wrong.contains(&"44642725".to_string()));
+    }
+}
@@ -0,0 +1,204 @@
+//! per-phase timing for the days, so "3 hr on debug, few minutes on release"
+//! can be a number we measure instead of a guess.
+//!
+//! every sample parses the input and runs both parts on the parsed result.
+//! results go to `bench/<commit>.json` so two commits can be compared.
+
+use std::{
+    fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use common::Solution;
+use serde::{Deserialize, Serialize};
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// raw samples for each phase of one day
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+    /// the day's own `Solution::bench_phases`
+    pub extra: Vec<(&'static str, Vec<Duration>)>,
+}
+
+impl Timings {
+    pub fn phases(&self) -> Vec<(&'static str, &[Duration])> {
+        let mut phases = vec![
+            (PHASES[0], self.parse.as_slice()),
+            (PHASES[1], &self.part1),
+            (PHASES[2], &self.part2),
+        ];
+        phases.extend(
+            self.extra
+                .iter()
+                .map(|(phase, samples)| (*phase, samples.as_slice())),
+        );
+        phases
+    }
+}
+
+pub fn sample<S: Solution>(lines: &[String], samples: usize) -> Result<Timings, S::Error> {
+    let extra = S::bench_phases();
+    let mut timings = Timings {
+        extra: extra.iter().map(|(phase, _)| (*phase, vec![])).collect(),
+        ..Timings::default()
+    };
+    for _ in 0..samples {
+        let start = Instant::now();
+        let input = S::parse(black_box(lines))?;
+        timings.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&input));
+        timings.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&input));
+        timings.part2.push(start.elapsed());
+
+        for ((_, run), (_, samples)) in extra.iter().zip(&mut timings.extra) {
+            let start = Instant::now();
+            run(black_box(&input));
+            samples.push(start.elapsed());
+        }
+    }
+    Ok(timings)
+}
+
+/// nearest-rank percentile, `p` in `0.0..=1.0`
+pub fn percentile(samples: &[Duration], p: f64) -> Duration {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct PhaseResult {
+    pub day: u8,
+    pub phase: String,
+    pub median_ns: u128,
+    pub p95_ns: u128,
+}
+
+impl PhaseResult {
+    pub fn new(day: u8, phase: &str, samples: &[Duration]) -> Self {
+        Self {
+            day,
+            phase: phase.to_string(),
+            median_ns: percentile(samples, 0.5).as_nanos(),
+            p95_ns: percentile(samples, 0.95).as_nanos(),
+        }
+    }
+}
+
+/// one `bench/<commit>.json`
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Report {
+    pub commit: String,
+    /// the working tree had uncommitted changes, so `commit` is only roughly right
+    pub dirty: bool,
+    pub samples: usize,
+    pub results: Vec<PhaseResult>,
+}
+
+impl Report {
+    pub fn path(root: &Path, commit: &str) -> PathBuf {
+        root.join("bench").join(format!("{commit}.json"))
+    }
+
+    pub fn load(root: &Path, commit: &str) -> Result<Self, String> {
+        let path = Self::path(root, commit);
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        serde_json::from_str(&raw).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<PathBuf, String> {
+        let path = Self::path(root, &self.commit);
+        let write = || -> std::io::Result<()> {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, serde_json::to_string_pretty(self)? + "\n")
+        };
+        write().map_err(|e| format!("could not write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn find(&self, day: u8, phase: &str) -> Option<&PhaseResult> {
+        self.results
+            .iter()
+            .find(|r| r.day == day && r.phase == phase)
+    }
+}
+
+/// short hash of HEAD and whether the tree is dirty
+pub fn current_commit(root: &Path) -> Result<(String, bool), String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .ok_or(format!("`git {}` failed", args.join(" ")))
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Ok((commit, dirty))
+}
+
+/// `+12.3%` slower / `-4.0%` faster than the baseline
+pub fn change(baseline_ns: u128, now_ns: u128) -> String {
+    if baseline_ns == 0 {
+        return "-".to_string();
+    }
+    let ratio = now_ns as f64 / baseline_ns as f64 - 1.0;
+    format!("{:+.1}%", ratio * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let samples: Vec<_> = (1..=20).rev().map(ms).collect();
+        assert_eq!(percentile(&samples, 0.5), ms(10));
+        assert_eq!(percentile(&samples, 0.95), ms(19));
+        assert_eq!(percentile(&[ms(7)], 0.5), ms(7));
+        assert_eq!(percentile(&[ms(7)], 0.95), ms(7));
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let report = Report {
+            commit: "abc1234".to_string(),
+            dirty: false,
+            samples: 3,
+            results: vec![PhaseResult::new(5, "parse", &[ms(1), ms(2), ms(3)])],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        let back: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, report);
+        assert_eq!(back.find(5, "parse").unwrap().median_ns, 2_000_000);
+        assert!(back.find(5, "part1").is_none());
+    }
+
+    #[test]
+    fn test_change() {
+        assert_eq!(change(100, 150), "+50.0%");
+        assert_eq!(change(200, 150), "-25.0%");
+        assert_eq!(change(0, 150), "-");
+    }
+}
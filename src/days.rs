@@ -0,0 +1,146 @@
+use std::str::FromStr;
+
+use common::{render, Solution};
+
+use crate::bench::{self, Timings};
+
+/// one implemented day. answers are stringified so days with different
+/// return types (`u128` for day02, `u64` for day05...) fit in the same table,
+/// and parse errors come back already rendered against the input
+pub struct Day {
+    pub number: u8,
+    pub pt1: fn(&[String]) -> Result<String, String>,
+    pub pt2: fn(&[String]) -> Result<String, String>,
+    /// time parse, part 1 and part 2 separately over this many samples
+    pub bench: fn(&[String], usize) -> Result<Timings, String>,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            pt1: |lines| {
+                S::solve_part1(lines)
+                    .map(|a| a.to_string())
+                    .map_err(|e| render(&e, lines))
+            },
+            pt2: |lines| {
+                S::solve_part2(lines)
+                    .map(|a| a.to_string())
+                    .map_err(|e| render(&e, lines))
+            },
+            bench: |lines, samples| {
+                bench::sample::<S>(lines, samples).map_err(|e| render(&e, lines))
+            },
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/// which days to run, as written on the command line
+///
+/// `5`, `1..5`, `1..=5` or `all`
+#[derive(Clone, Debug, PartialEq)]
+pub enum DaySelection {
+    One(u8),
+    Range(u8, u8),
+    All,
+}
+
+impl DaySelection {
+    /// a single day has to exist, but ranges quietly skip the stub days that
+    /// haven't been solved yet
+    pub fn resolve(&self) -> Result<Vec<&'static Day>, String> {
+        match self {
+            DaySelection::One(n) => find(*n)
+                .map(|d| vec![d])
+                .ok_or(format!("day {n} is not implemented yet")),
+            DaySelection::Range(start, end) => Ok(DAYS
+                .iter()
+                .filter(|d| (*start..=*end).contains(&d.number))
+                .collect()),
+            DaySelection::All => Ok(DAYS.iter().collect()),
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |n: &str| {
+            n.parse::<u8>()
+                .ok()
+                .filter(|n| (1..=25).contains(n))
+                .ok_or(format!("'{n}' is not a day between 1 and 25"))
+        };
+        if s == "all" {
+            Ok(DaySelection::All)
+        } else if let Some((start, end)) = s.split_once("..=") {
+            Ok(DaySelection::Range(day(start)?, day(end)?))
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end = day(end)?;
+            if end == 1 {
+                return Err(format!("'{s}' is an empty range"));
+            }
+            Ok(DaySelection::Range(day(start)?, end - 1))
+        } else {
+            Ok(DaySelection::One(day(s)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!("5".parse(), Ok(DaySelection::One(5)));
+        assert_eq!("1..=5".parse(), Ok(DaySelection::Range(1, 5)));
+        assert_eq!("1..5".parse(), Ok(DaySelection::Range(1, 4)));
+        assert_eq!("all".parse(), Ok(DaySelection::All));
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("1..1".parse::<DaySelection>().is_err());
+    }
+
+    // what `aoc new 6` writes, kept the same by `scaffold::tests`
+    mod day06 {
+        include!("testdata/day06.rs");
+    }
+
+    /// a day straight from `aoc new` goes through the runner without
+    /// stopping it
+    #[test]
+    fn test_runs_a_scaffolded_day() {
+        let day = Day::of::<day06::Day06>();
+        let lines = vec!["Time: 7 15 30".to_string()];
+        for solve in [day.pt1, day.pt2] {
+            let answer = solve(&lines).unwrap();
+            assert_eq!(answer, "unsolved");
+            assert!(!answers::check(None, &answer).is_failure());
+        }
+        assert!((day.bench)(&lines, 2).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_skips_stubs() {
+        let days = DaySelection::Range(4, 9).resolve().unwrap();
+        let numbers: Vec<_> = days.iter().map(|d| d.number).collect();
+        assert_eq!(numbers, vec![4, 5]);
+        assert!(DaySelection::One(9).resolve().is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! `aoc examples`: pull the worked examples out of `descriptions/dayN.md`.
+//!
+//! each part's example is the first fenced block in its half of the
+//! description, and its answer is the last emphasized code span (`**`35`**`),
+//! which is how the puzzles spell out "so the answer here is 35". part 2
+//! reuses part 1's example unless it brings its own.
+//!
+//! the inputs go to `examples/dayN/exK.txt` and the answers to
+//! `examples/answers.toml`, in the same format as `answers.toml` with `exK`
+//! as the input name. the tests at the bottom run every day against them.
+//!
+//! descriptions fetched before `aoc fetch` wrote markdown are the page
+//! flattened to text, with no fences or emphasis to go on. their days keep
+//! whatever examples are checked in until `aoc fetch` writes them again.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::answers::Answers;
+
+/// one example input and whichever answers the description gives for it
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// the first fenced block in `section`, without its fences
+fn first_code_block(section: &str) -> Option<String> {
+    let mut lines = section.lines();
+    let fence = lines.find(|l| l.starts_with("```"))?.trim_end();
+    let mut body = String::new();
+    for line in lines {
+        if line.trim_end() == fence {
+            return Some(body);
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    None
+}
+
+/// the last `**`answer`**` in `section`
+fn last_answer(section: &str) -> Option<String> {
+    section
+        .rmatch_indices("**`")
+        .filter_map(|(i, _)| {
+            let rest = &section[i + 3..];
+            rest.find("`**").map(|end| rest[..end].to_string())
+        })
+        .find(|answer| !answer.is_empty() && !answer.contains('`'))
+}
+
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let (one, two) = match markdown.find("--- Part Two ---") {
+        Some(i) => {
+            let heading = markdown[..i].rfind('\n').map_or(0, |n| n + 1);
+            (&markdown[..heading], Some(&markdown[heading..]))
+        }
+        None => (markdown, None),
+    };
+
+    let mut examples = vec![];
+    if let Some(input) = first_code_block(one) {
+        examples.push(Example {
+            input,
+            part1: last_answer(one),
+            part2: None,
+        });
+    }
+    let Some(two) = two else {
+        return examples;
+    };
+    let answer = last_answer(two);
+    match (first_code_block(two), examples.first_mut()) {
+        (Some(input), Some(first)) if input == first.input => first.part2 = answer,
+        (Some(input), _) => examples.push(Example {
+            input,
+            part1: None,
+            part2: answer,
+        }),
+        (None, Some(first)) => first.part2 = answer,
+        (None, None) => {}
+    }
+    examples
+}
+
+pub fn example_path(root: &Path, day: u8, n: usize) -> PathBuf {
+    root.join("examples")
+        .join(format!("day{day}"))
+        .join(format!("ex{n}.txt"))
+}
+
+pub fn answers_path(root: &Path) -> PathBuf {
+    root.join("examples").join("answers.toml")
+}
+
+/// `examples/answers.toml` for every day's examples, numbered from `ex1`
+pub fn answers_toml(days: &[(u8, Vec<Example>)]) -> String {
+    let mut out =
+        String::from("# generated by `aoc examples` from descriptions/, don't edit by hand\n");
+    for (day, examples) in days {
+        for (i, example) in examples.iter().enumerate() {
+            let parts = [("part1", &example.part1), ("part2", &example.part2)];
+            if parts.iter().all(|(_, answer)| answer.is_none()) {
+                continue;
+            }
+            let _ = write!(out, "\n[day{day:02}.ex{}]\n", i + 1);
+            for (part, answer) in parts {
+                if let Some(answer) = answer {
+                    let answer = toml::Value::String(answer.clone());
+                    let _ = writeln!(out, "{part} = {{ answer = {answer} }}");
+                }
+            }
+        }
+    }
+    out
+}
+
+/// whether `description` is markdown from `aoc fetch` rather than the old
+/// flattened text, which starts with the title run into the first line
+fn is_markdown(description: &str) -> bool {
+    description.starts_with("## ")
+}
+
+/// the examples already in `examples/` for `day`, with their answers from
+/// `answers`
+fn checked_in(root: &Path, day: u8, answers: &Answers) -> Vec<Example> {
+    let mut examples = vec![];
+    for n in 1.. {
+        let Ok(input) = fs::read_to_string(example_path(root, day, n)) else {
+            break;
+        };
+        let answer = |part| answers.get(day, &format!("ex{n}"), part)?.answer.clone();
+        examples.push(Example {
+            input,
+            part1: answer(1),
+            part2: answer(2),
+        });
+    }
+    examples
+}
+
+/// every day's examples under `root`: extracted from its description, or
+/// the checked-in ones if that's still flattened text
+pub fn collect(root: &Path) -> Result<Vec<(u8, Vec<Example>)>, String> {
+    let path = answers_path(root);
+    let answers = if path.exists() {
+        Answers::load(&path)?
+    } else {
+        Answers::default()
+    };
+    let mut days = vec![];
+    for day in 1..=25u8 {
+        let description = root.join("descriptions").join(format!("day{day}.md"));
+        let Ok(description) = fs::read_to_string(&description) else {
+            continue;
+        };
+        let examples = if is_markdown(&description) {
+            extract(&description)
+        } else {
+            checked_in(root, day, &answers)
+        };
+        days.push((day, examples));
+    }
+    Ok(days)
+}
+
+/// `collect` every day's examples under `root` and write them to
+/// `examples/`, returning what was written
+pub fn write_all(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let days = collect(root)?;
+
+    let write = |path: &Path, contents: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, contents))
+            .map_err(|e| format!("could not write {}: {e}", path.display()))
+    };
+    let mut written = vec![];
+    for (day, examples) in &days {
+        for (i, example) in examples.iter().enumerate() {
+            let path = example_path(root, *day, i + 1);
+            write(&path, &example.input)?;
+            written.push(path);
+        }
+    }
+    let path = answers_path(root);
+    write(&path, &answers_toml(&days))?;
+    written.push(path);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers, days::DAYS};
+
+    const BOTH_PARTS: &str = "## --- Day 9: Test ---
+
+For example:
+
+```
+1 2
+3 4
+```
+
+So the answer is **`10`**, not **`3`** or **`7`**. Just **`10`**.
+
+## --- Part Two ---
+
+Now multiply:
+
+```
+x
+```
+
+That gives **`24`**.
+";
+
+    #[test]
+    fn test_extracts_examples_and_answers() {
+        assert_eq!(
+            extract(BOTH_PARTS),
+            vec![
+                Example {
+                    input: "1 2\n3 4\n".to_string(),
+                    part1: Some("10".to_string()),
+                    part2: None,
+                },
+                Example {
+                    input: "x\n".to_string(),
+                    part1: None,
+                    part2: Some("24".to_string()),
+                },
+            ]
+        );
+
+        // without its own example part 2 reuses part 1's
+        let reused = BOTH_PARTS.replace("```\nx\n```", "");
+        assert_eq!(
+            extract(&reused),
+            vec![Example {
+                input: "1 2\n3 4\n".to_string(),
+                part1: Some("10".to_string()),
+                part2: Some("24".to_string()),
+            }]
+        );
+        assert_eq!(extract("no examples here"), vec![]);
+    }
+
+    #[test]
+    fn test_answers_toml_parses() {
+        let days = vec![(9, extract(BOTH_PARTS))];
+        let answers: Answers = answers_toml(&days).parse().unwrap();
+        assert_eq!(answers.inputs(9), vec!["ex1", "ex2"]);
+        assert_eq!(
+            answers::check(answers.get(9, "ex2", 2), "24"),
+            answers::Verdict::Correct
+        );
+        assert!(answers.get(9, "ex2", 1).is_none());
+    }
+
+    fn root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).to_path_buf()
+    }
+
+    /// the checked-in examples are what `aoc examples` would write today
+    #[test]
+    fn test_examples_are_up_to_date() {
+        let root = root();
+        let days = collect(&root).unwrap();
+        for (day, examples) in &days {
+            for (i, example) in examples.iter().enumerate() {
+                let path = example_path(&root, *day, i + 1);
+                assert_eq!(
+                    fs::read_to_string(&path).ok().as_ref(),
+                    Some(&example.input),
+                    "{} is stale, run `aoc examples`",
+                    path.display()
+                );
+            }
+        }
+        assert_eq!(
+            fs::read_to_string(answers_path(&root)).unwrap(),
+            answers_toml(&days),
+            "examples/answers.toml is stale, run `aoc examples`"
+        );
+    }
+
+    #[test]
+    fn test_flattened_descriptions_keep_their_examples() {
+        let root = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("descriptions")).unwrap();
+        fs::write(root.join("descriptions/day9.md"), BOTH_PARTS).unwrap();
+        fs::write(
+            root.join("descriptions/day8.md"),
+            "--- Day 8: Test ---For example:\n\n1 2\n\nSo the answer is 3.\n",
+        )
+        .unwrap();
+        write_all(&root).unwrap();
+        // day 8 has nothing to extract and nothing checked in
+        assert_eq!(collect(&root).unwrap()[0], (8, vec![]));
+
+        fs::create_dir_all(root.join("examples/day8")).unwrap();
+        fs::write(example_path(&root, 8, 1), "1 2\n").unwrap();
+        let answers = fs::read_to_string(answers_path(&root)).unwrap()
+            + "\n[day08.ex1]\npart1 = { answer = \"3\" }\n";
+        fs::write(answers_path(&root), answers).unwrap();
+        let days = collect(&root).unwrap();
+        assert_eq!(
+            days[0],
+            (
+                8,
+                vec![Example {
+                    input: "1 2\n".to_string(),
+                    part1: Some("3".to_string()),
+                    part2: None,
+                }]
+            )
+        );
+        assert_eq!(days[1].1, extract(BOTH_PARTS));
+        write_all(&root).unwrap();
+        assert_eq!(collect(&root).unwrap(), days);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_days_solve_their_examples() {
+        let root = root();
+        let answers = Answers::load(&answers_path(&root)).unwrap();
+        let mut checked = 0;
+        for day in DAYS {
+            for input in answers.inputs(day.number) {
+                let n = input.strip_prefix("ex").and_then(|n| n.parse().ok());
+                let Some(n) = n else { continue };
+                let source = common::input::Source::File(example_path(&root, day.number, n));
+                let lines = common::input::read_source(&source).unwrap();
+                for (part, solve) in [(1, day.pt1), (2, day.pt2)] {
+                    // only run parts with an answer, some are too slow to run blind
+                    let Some(recorded) = answers.get(day.number, &input, part) else {
+                        continue;
+                    };
+                    let got = solve(&lines).unwrap();
+                    // straight from `aoc new`, nothing to check yet
+                    if got == common::Unsolved.to_string() {
+                        continue;
+                    }
+                    assert_eq!(
+                        answers::check(Some(recorded), &got),
+                        answers::Verdict::Correct,
+                        "day {} part {part} on {input}",
+                        day.number
+                    );
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked >= DAYS.len(), "only {checked} examples checked");
+    }
+}
@@ -0,0 +1,308 @@
+//! `aoc fetch`: download a day's puzzle page and input.
+//!
+//! the session cookie comes from `$AOC_SESSION`, or failing that the first line
+//! of `$XDG_CONFIG_HOME/aoc/session` (`~/.config/aoc/session`). responses are
+//! cached under `.aoc-cache/` in the workspace so rerunning doesn't hit the
+//! site again, and requests are spaced out by a minimum interval.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = "aoc fetch (github.com/cpdean/advent_of_code_2023)";
+
+pub fn session() -> Result<String, String> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("aoc").join("session"))
+        .ok_or(format!(
+            "set {SESSION_VAR} to your adventofcode.com session cookie"
+        ))?;
+    let raw = fs::read_to_string(&config).map_err(|e| {
+        format!(
+            "set {SESSION_VAR} or put your session cookie in {}: {e}",
+            config.display()
+        )
+    })?;
+    raw.lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .ok_or(format!("{} is empty", config.display()))
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(
+        base_url: &str,
+        session: String,
+        cache_dir: PathBuf,
+        min_interval: Duration,
+    ) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+            min_interval,
+        }
+    }
+
+    /// `GET base_url + path`, answered from the cache unless `refresh` is set
+    pub fn get(&self, path: &str, cache_name: &str, refresh: bool) -> Result<String, String> {
+        let cached = self.cache_dir.join(cache_name);
+        if !refresh {
+            if let Ok(body) = fs::read_to_string(&cached) {
+                return Ok(body);
+            }
+        }
+
+        self.wait_for_interval();
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        self.mark_request();
+        let body = match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| format!("reading {url}: {e}"))?,
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                return Err(format!("{url} returned {code}: {}", body.trim()));
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+
+        fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| fs::write(&cached, &body))
+            .map_err(|e| format!("could not cache {}: {e}", cached.display()))?;
+        Ok(body)
+    }
+
+    fn last_request_file(&self) -> PathBuf {
+        self.cache_dir.join("last-request")
+    }
+
+    /// sleep until `min_interval` has passed since the last request, even if
+    /// that request came from a previous run
+    fn wait_for_interval(&self) {
+        let last = fs::read_to_string(self.last_request_file())
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(elapsed) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+    }
+
+    fn mark_request(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let _ = fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| fs::write(self.last_request_file(), now.to_string()));
+    }
+}
+
+/// where `fetch_day` put things
+#[derive(Debug, PartialEq)]
+pub struct Fetched {
+    pub description: PathBuf,
+    pub input: PathBuf,
+}
+
+/// download a day and write `descriptions/dayN.md` and `data/N.1.txt` under `root`
+///
+/// `refresh` only bypasses the cache for the puzzle page, the input never changes
+/// once it's been handed out but the page grows a part 2
+pub fn fetch_day(client: &Client, day: u8, root: &Path, refresh: bool) -> Result<Fetched, String> {
+    let page = client.get(
+        &format!("/2023/day/{day}"),
+        &format!("day{day}.html"),
+        refresh,
+    )?;
+    let input = client.get(
+        &format!("/2023/day/{day}/input"),
+        &format!("day{day}.input.txt"),
+        false,
+    )?;
+
+    let description = root.join("descriptions").join(format!("day{day}.md"));
+    let input_path = root.join("data").join(format!("{day}.1.txt"));
+    let write = |path: &Path, contents: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, contents))
+            .map_err(|e| format!("could not write {}: {e}", path.display()))
+    };
+    let markdown = crate::markdown::puzzle_description(&page)
+        .ok_or("no puzzle description on the page, is the session cookie right?".to_string())?;
+    write(&description, &markdown)?;
+    write(&input_path, &input)?;
+    Ok(Fetched {
+        description,
+        input: input_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    const PAGE: &str = "<html><body><main><article class=\"day-desc\"><h2>--- Day 5: Seeds ---</h2><p>The almanac &amp; the <em>seeds</em>.</p></article></main></body></html>";
+
+    /// a tiny http server answering from `routes`, remembering every request line
+    /// and cookie it saw
+    fn mock_server(
+        routes: Vec<(&'static str, u16, &'static str)>,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(vec![]));
+        let log = seen.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(c) = header.strip_prefix("Cookie: ") {
+                        cookie = c.trim().to_string();
+                    }
+                }
+                let path = request.split(' ').nth(1).unwrap_or("").to_string();
+                log.lock().unwrap().push(format!("{path} {cookie}"));
+                let (status, body) = routes
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .map(|(_, s, b)| (*s, *b))
+                    .unwrap_or((404, "not found"));
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (base, seen)
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_fetch_writes_description_and_input() {
+        let (base, seen) = mock_server(vec![
+            ("/2023/day/5", 200, PAGE),
+            ("/2023/day/5/input", 200, "seeds: 79 14 55 13\n"),
+        ]);
+        let root = scratch("writes");
+        let client = Client::new(
+            &base,
+            "abc".to_string(),
+            root.join(".aoc-cache"),
+            Duration::ZERO,
+        );
+
+        let fetched = fetch_day(&client, 5, &root, false).unwrap();
+        assert_eq!(fetched.input, root.join("data/5.1.txt"));
+        assert_eq!(
+            fs::read_to_string(&fetched.input).unwrap(),
+            "seeds: 79 14 55 13\n"
+        );
+        let description = fs::read_to_string(&fetched.description).unwrap();
+        assert_eq!(
+            description,
+            "## --- Day 5: Seeds ---\n\nThe almanac & the **seeds**.\n"
+        );
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["/2023/day/5 session=abc", "/2023/day/5/input session=abc"]
+        );
+
+        // everything is cached now, so a second fetch doesn't touch the server
+        fetch_day(&client, 5, &root, false).unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 2);
+
+        // refreshing only refetches the page
+        fetch_day(&client, 5, &root, true).unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_fetch_reports_http_errors() {
+        let (base, _) = mock_server(vec![(
+            "/2023/day/25",
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let root = scratch("errors");
+        let client = Client::new(
+            &base,
+            "abc".to_string(),
+            root.join(".aoc-cache"),
+            Duration::ZERO,
+        );
+
+        let e = fetch_day(&client, 25, &root, false).unwrap_err();
+        assert!(e.contains("returned 404"), "{e}");
+        assert!(e.contains("before it unlocks"), "{e}");
+        assert!(!root.join("data/25.1.txt").exists());
+    }
+
+    #[test]
+    fn test_min_interval_spaces_requests() {
+        let (base, _) = mock_server(vec![("/a", 200, "a"), ("/b", 200, "b")]);
+        let root = scratch("interval");
+        let interval = Duration::from_millis(300);
+        let client = Client::new(&base, "abc".to_string(), root.join(".aoc-cache"), interval);
+
+        let start = std::time::Instant::now();
+        client.get("/a", "a", false).unwrap();
+        client.get("/b", "b", false).unwrap();
+        assert!(start.elapsed() >= interval, "{:?}", start.elapsed());
+    }
+}
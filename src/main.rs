@@ -1,3 +1,279 @@
-fn main() {
-    println!("Hello, world!");
+use std::{process::ExitCode, time::Duration};
+
+use clap::{Parser, Subcommand};
+
+mod answers;
+mod bench;
+mod days;
+mod examples;
+mod fetch;
+mod markdown;
+mod scaffold;
+
+use answers::Answers;
+use bench::{PhaseResult, Report};
+use days::DaySelection;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "advent of code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// run one or more days and print a table of answers
+    Run {
+        /// a day (`5`), a range (`1..=5`, `1..5`) or `all`
+        days: DaySelection,
+        /// only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// which input to use: a name like `2` for `data/N.2.txt`, a path, or `-` for stdin
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// time parsing and each part separately, saving the results to `bench/<commit>.json`
+    Bench {
+        /// a day (`5`), a range (`1..=5`, `1..5`) or `all`
+        #[arg(default_value = "all")]
+        days: DaySelection,
+        /// how many times to run each day
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        samples: u64,
+        /// which input to use: a name like `2` for `data/N.2.txt`, a path, or `-` for stdin
+        #[arg(long)]
+        input: Option<String>,
+        /// compare against the results saved for this commit
+        #[arg(long)]
+        baseline: Option<String>,
+        /// print the results without writing them to `bench/`
+        #[arg(long)]
+        no_save: bool,
+    },
+    /// download a day's puzzle description and input
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// download the puzzle page again instead of using the cached copy,
+        /// e.g. once part 2 is unlocked
+        #[arg(long)]
+        refresh: bool,
+        /// seconds to leave between requests to the site
+        #[arg(long, default_value_t = 5)]
+        min_interval: u64,
+        #[arg(long, default_value = fetch::BASE_URL, hide = true)]
+        base_url: String,
+    },
+    /// run days against every input in `answers.toml` and report any that changed
+    Verify {
+        /// a day (`5`), a range (`1..=5`, `1..5`) or `all`
+        #[arg(default_value = "all")]
+        days: DaySelection,
+    },
+    /// extract the worked examples and their answers from `descriptions/` into `examples/`
+    Examples,
+    /// turn a stub day crate into a solution skeleton and add it to the runner
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+/// fold the day into a rendered parse error's headline
+fn day_error(day: u8, e: String) -> String {
+    format!("day {day}: {}", e.strip_prefix("error: ").unwrap_or(&e))
+}
+
+fn run(selection: &DaySelection, part: Option<u8>, input: Option<&str>) -> Result<(), String> {
+    let days = selection.resolve()?;
+    println!("{:>3}  {:>4}  answer", "day", "part");
+    for day in days {
+        let lines = common::read_input(day.number, input).map_err(|e| e.to_string())?;
+        let parts = [(1, day.pt1), (2, day.pt2)];
+        for (n, solve) in parts {
+            if part.is_some_and(|p| p != n) {
+                continue;
+            }
+            let answer = solve(&lines).map_err(|e| day_error(day.number, e))?;
+            println!("{:>3}  {:>4}  {}", day.number, n, answer);
+        }
+    }
+    Ok(())
+}
+
+fn verify(selection: &DaySelection) -> Result<(), String> {
+    let days = selection.resolve()?;
+    let root = common::input::workspace_root().map_err(|e| e.to_string())?;
+    let answers = Answers::load(&root.join("answers.toml"))?;
+    let data_dir = common::input::data_dir().map_err(|e| e.to_string())?;
+
+    let mut checked = 0;
+    let mut failures = 0;
+    println!(
+        "{:>3}  {:>4}  {:<8}  {:<16}  result",
+        "day", "part", "input", "answer"
+    );
+    for day in days {
+        for input in answers.inputs(day.number) {
+            let lines = match common::input::resolve_in(day.number, &input, &data_dir)
+                .and_then(|source| common::input::read_source(&source))
+            {
+                Ok(lines) => lines,
+                Err(e) => {
+                    println!("{:>3}  {:>4}  {:<8}  skipped: {e}", day.number, "", input);
+                    continue;
+                }
+            };
+            for (n, solve) in [(1, day.pt1), (2, day.pt2)] {
+                let (answer, result) = match solve(&lines) {
+                    Ok(answer) => {
+                        let verdict = answers::check(answers.get(day.number, &input, n), &answer);
+                        failures += verdict.is_failure() as usize;
+                        (answer, verdict.to_string())
+                    }
+                    Err(e) => {
+                        failures += 1;
+                        eprintln!("{}", day_error(day.number, e));
+                        ("-".to_string(), "PARSE ERROR".to_string())
+                    }
+                };
+                checked += 1;
+                println!(
+                    "{:>3}  {:>4}  {:<8}  {:<16}  {result}",
+                    day.number, n, input, answer
+                );
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(format!(
+            "{failures} of {checked} answers don't match answers.toml"
+        ));
+    }
+    Ok(())
+}
+
+struct BenchArgs<'a> {
+    samples: usize,
+    input: Option<&'a str>,
+    baseline: Option<&'a str>,
+    save: bool,
+}
+
+fn bench(selection: &DaySelection, args: BenchArgs) -> Result<(), String> {
+    let days = selection.resolve()?;
+    let root = common::input::workspace_root().map_err(|e| e.to_string())?;
+    let (commit, dirty) = bench::current_commit(&root)?;
+    let baseline = args
+        .baseline
+        .map(|commit| Report::load(&root, commit))
+        .transpose()?;
+
+    let mut results = vec![];
+    println!(
+        "{:>3}  {:<7}  {:>12}  {:>12}  vs baseline",
+        "day", "phase", "median", "p95"
+    );
+    for day in days {
+        let lines = common::read_input(day.number, args.input).map_err(|e| e.to_string())?;
+        let timings = (day.bench)(&lines, args.samples).map_err(|e| day_error(day.number, e))?;
+        for (phase, samples) in timings.phases() {
+            let result = PhaseResult::new(day.number, phase, samples);
+            let change = baseline
+                .as_ref()
+                .and_then(|b| b.find(day.number, phase))
+                .map(|b| bench::change(b.median_ns, result.median_ns))
+                .unwrap_or_default();
+            println!(
+                "{:>3}  {:<7}  {:>12.3?}  {:>12.3?}  {change}",
+                day.number,
+                phase,
+                bench::percentile(samples, 0.5),
+                bench::percentile(samples, 0.95),
+            );
+            results.push(result);
+        }
+    }
+
+    if args.save {
+        let report = Report {
+            commit,
+            dirty,
+            samples: args.samples,
+            results,
+        };
+        let path = report.save(&root)?;
+        println!("saved {}", path.display());
+    }
+    Ok(())
+}
+
+fn fetch(day: u8, refresh: bool, min_interval: u64, base_url: &str) -> Result<(), String> {
+    let root = common::input::workspace_root().map_err(|e| e.to_string())?;
+    let client = fetch::Client::new(
+        base_url,
+        fetch::session()?,
+        root.join(".aoc-cache"),
+        Duration::from_secs(min_interval),
+    );
+    let fetched = fetch::fetch_day(&client, day, &root, refresh)?;
+    println!("wrote {}", fetched.description.display());
+    println!("wrote {}", fetched.input.display());
+    Ok(())
+}
+
+fn write_examples() -> Result<(), String> {
+    let root = common::input::workspace_root().map_err(|e| e.to_string())?;
+    for path in examples::write_all(&root)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn new_day(day: u8) -> Result<(), String> {
+    let root = common::input::workspace_root().map_err(|e| e.to_string())?;
+    for path in scaffold::new_day(&root, day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Run { days, part, input } => run(days, *part, input.as_deref()),
+        Command::Bench {
+            days,
+            samples,
+            input,
+            baseline,
+            no_save,
+        } => bench(
+            days,
+            BenchArgs {
+                samples: *samples as usize,
+                input: input.as_deref(),
+                baseline: baseline.as_deref(),
+                save: !no_save,
+            },
+        ),
+        Command::Fetch {
+            day,
+            refresh,
+            min_interval,
+            base_url,
+        } => fetch(*day, *refresh, *min_interval, base_url),
+        Command::Verify { days } => verify(days),
+        Command::Examples => write_examples(),
+        Command::New { day } => new_day(*day),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
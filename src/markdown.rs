@@ -0,0 +1,457 @@
+//! turns the puzzle page's `<article class="day-desc">` html into markdown.
+//!
+//! only handles what the puzzle pages actually use: `h2`, `p`, `pre`/`code`,
+//! `em`, `ul`/`li`, `a` and `span`. `<pre><code>` becomes a fenced block with
+//! its text kept exactly, `<em>` becomes `**emphasis**`, and once part 2 is
+//! unlocked its article follows part 1's under its own heading.
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Element {
+        tag: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    fn attr(&self, name: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    /// all the text under this node, tags dropped
+    fn text(&self) -> String {
+        match self {
+            Node::Text(t) => t.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+
+    fn is(&self, name: &str) -> bool {
+        matches!(self, Node::Element { tag, .. } if tag == name)
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+}
+
+const VOID: [&str; 6] = ["br", "hr", "img", "input", "meta", "link"];
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_attrs(raw: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![];
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let end = after[1..].find(q).map(|i| i + 1).unwrap_or(after.len());
+                    (&after[1..end], after.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+        if !name.is_empty() {
+            attrs.push((name, value));
+        }
+    }
+    attrs
+}
+
+fn element(tag: String, attrs: Vec<(String, String)>) -> Node {
+    Node::Element {
+        tag,
+        attrs,
+        children: vec![],
+    }
+}
+
+/// add `node` to the innermost open element
+fn push(stack: &mut [Node], node: Node) {
+    if let Some(Node::Element { children, .. }) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+/// a forgiving html parser: unknown closing tags are ignored and unclosed
+/// elements are closed by whatever closes their parent
+fn parse(html: &str) -> Vec<Node> {
+    // open elements, the bottom one is a stand-in for the document
+    let mut stack = vec![element(String::new(), vec![])];
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push(&mut stack, Node::Text(decode_entities(rest)));
+            break;
+        };
+        if lt > 0 {
+            push(&mut stack, Node::Text(decode_entities(&rest[..lt])));
+        }
+        rest = &rest[lt..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map(|i| &comment[i + 3..]).unwrap_or("");
+            continue;
+        }
+        let Some(gt) = rest.find('>') else {
+            push(&mut stack, Node::Text(decode_entities(rest)));
+            break;
+        };
+        let tag = &rest[1..gt];
+        rest = &rest[gt + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_lowercase();
+            if let Some(open) = stack.iter().skip(1).rposition(|n| n.is(&name)) {
+                while stack.len() > open + 1 {
+                    let closed = stack.pop().unwrap();
+                    push(&mut stack, closed);
+                }
+            }
+            continue;
+        }
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_lowercase();
+        let attrs = parse_attrs(&tag[name_end..]);
+        if self_closing || VOID.contains(&name.as_str()) {
+            push(&mut stack, element(name, attrs));
+        } else {
+            stack.push(element(name, attrs));
+        }
+    }
+    while stack.len() > 1 {
+        let closed = stack.pop().unwrap();
+        push(&mut stack, closed);
+    }
+    match stack.pop() {
+        Some(Node::Element { children, .. }) => children,
+        _ => vec![],
+    }
+}
+
+fn find_all<'a>(nodes: &'a [Node], matches: &dyn Fn(&Node) -> bool, out: &mut Vec<&'a Node>) {
+    for node in nodes {
+        if matches(node) {
+            out.push(node);
+        } else {
+            find_all(node.children(), matches, out);
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '`' | '\\' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// backticks around `code`, using more of them if the code has its own
+fn code_span(code: &str) -> String {
+    let longest_run = code
+        .split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0);
+    let ticks = "`".repeat(longest_run + 1);
+    let pad = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{ticks}{pad}{code}{pad}{ticks}")
+}
+
+fn inline<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(&escape(t)),
+            Node::Element { tag, children, .. } => match tag.as_str() {
+                "em" | "strong" | "b" => {
+                    let inner = inline(children);
+                    if inner.trim().is_empty() {
+                        out.push_str(&inner);
+                    } else {
+                        out.push_str(&format!("**{inner}**"));
+                    }
+                }
+                "code" => {
+                    let span = code_span(&node.text());
+                    // `<code><em>35</em></code>` is how the pages highlight answers
+                    let emphasized = children.iter().any(|c| c.is("em"));
+                    if emphasized {
+                        out.push_str(&format!("**{span}**"));
+                    } else {
+                        out.push_str(&span);
+                    }
+                }
+                "a" => {
+                    let href = node.attr("href").unwrap_or("");
+                    let href = if href.starts_with('/') {
+                        format!("{}{href}", crate::fetch::BASE_URL)
+                    } else {
+                        href.to_string()
+                    };
+                    out.push_str(&format!("[{}]({href})", inline(children)));
+                }
+                "br" => out.push('\n'),
+                _ => out.push_str(&inline(children)),
+            },
+        }
+    }
+    out
+}
+
+/// collapse html whitespace the way a browser would for normal text
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn fence(code: &str) -> String {
+    let longest_run = code
+        .lines()
+        .map(|l| l.len() - l.trim_start_matches('`').len())
+        .max()
+        .unwrap_or(0);
+    let ticks = "`".repeat(longest_run.max(2) + 1);
+    let body = if code.ends_with('\n') {
+        code.to_string()
+    } else {
+        format!("{code}\n")
+    };
+    format!("{ticks}\n{body}{ticks}")
+}
+
+fn blocks(nodes: &[Node], out: &mut Vec<String>) {
+    // loose text and inline elements between blocks make up a paragraph
+    let mut loose: Vec<&Node> = vec![];
+    let flush = |loose: &mut Vec<&Node>, out: &mut Vec<String>| {
+        let text = collapse(&inline(loose.drain(..)));
+        if !text.is_empty() {
+            out.push(text);
+        }
+    };
+    for node in nodes {
+        let Node::Element { tag, children, .. } = node else {
+            loose.push(node);
+            continue;
+        };
+        match tag.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                flush(&mut loose, out);
+                let level = tag[1..].parse::<usize>().unwrap_or(2);
+                out.push(format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    collapse(&inline(children))
+                ));
+            }
+            "p" => {
+                flush(&mut loose, out);
+                let text = collapse(&inline(children));
+                if !text.is_empty() {
+                    out.push(text);
+                }
+            }
+            "pre" => {
+                flush(&mut loose, out);
+                out.push(fence(&node.text()));
+            }
+            "ul" | "ol" => {
+                flush(&mut loose, out);
+                let items: Vec<_> = children
+                    .iter()
+                    .filter(|c| c.is("li"))
+                    .enumerate()
+                    .map(|(i, li)| {
+                        let marker = if tag == "ol" {
+                            format!("{}.", i + 1)
+                        } else {
+                            "-".to_string()
+                        };
+                        format!("{marker} {}", collapse(&inline(li.children())))
+                    })
+                    .collect();
+                out.push(items.join("\n"));
+            }
+            "div" | "article" | "section" | "main" => {
+                flush(&mut loose, out);
+                blocks(children, out);
+            }
+            _ => loose.push(node),
+        }
+    }
+    flush(&mut loose, out);
+}
+
+/// markdown for every `<article class="day-desc">` on a puzzle page, or `None`
+/// if there aren't any (not logged in, or the day isn't out yet)
+pub fn puzzle_description(page: &str) -> Option<String> {
+    let doc = parse(page);
+    let mut articles = vec![];
+    find_all(
+        &doc,
+        &|n| n.is("article") && n.attr("class") == Some("day-desc"),
+        &mut articles,
+    );
+    if articles.is_empty() {
+        return None;
+    }
+    let mut out = vec![];
+    for article in articles {
+        blocks(article.children(), &mut out);
+    }
+    Some(out.join("\n\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART_ONE: &str = r#"<article class="day-desc"><h2>--- Day 5: If You Give A Seed A Fertilizer ---</h2><p>You take the boat and find the gardener right where you were told he would be: managing a giant "garden" that looks more to you like a farm.</p>
+<p>For example:</p>
+<pre><code>seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+</code></pre>
+<ul>
+<li>Seed <code>79</code>, soil <code>81</code>.</li>
+<li>Seed <code>14</code>, soil <code>14</code>.</li>
+</ul>
+<p>So, the lowest location number in this example is <code><em>35</em></code>.</p>
+<p><em>What is the lowest location number that corresponds to any of the initial seed numbers?</em></p>
+</article>"#;
+
+    const PART_TWO: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Everyone will starve if you only plant such a <span title="Seeds">small</span> number of seeds. Re-reading the <a href="/2023/day/5">almanac</a>, it looks like the <code>seeds:</code> line actually describes <em>ranges of seed numbers</em>.</p>
+</article>"#;
+
+    #[test]
+    fn test_part_one() {
+        let page = format!("<html><body><main>{PART_ONE}<p>Your puzzle answer was <code>173706076</code>.</p></main></body></html>");
+        assert_eq!(
+            puzzle_description(&page).unwrap(),
+            "## --- Day 5: If You Give A Seed A Fertilizer ---
+
+You take the boat and find the gardener right where you were told he would be: managing a giant \"garden\" that looks more to you like a farm.
+
+For example:
+
+```
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+```
+
+- Seed `79`, soil `81`.
+- Seed `14`, soil `14`.
+
+So, the lowest location number in this example is **`35`**.
+
+**What is the lowest location number that corresponds to any of the initial seed numbers?**
+"
+        );
+    }
+
+    #[test]
+    fn test_both_parts() {
+        let page = format!("<main>{PART_ONE}<p>Your puzzle answer was <code>173706076</code>.</p>{PART_TWO}</main>");
+        let md = puzzle_description(&page).unwrap();
+        assert!(md.contains("**What is the lowest location number that corresponds to any of the initial seed numbers?**\n\n## --- Part Two ---\n\n"), "{md}");
+        assert!(md.ends_with("Everyone will starve if you only plant such a small number of seeds. Re-reading the [almanac](https://adventofcode.com/2023/day/5), it looks like the `seeds:` line actually describes **ranges of seed numbers**.\n"), "{md}");
+        assert!(!md.contains("Your puzzle answer"));
+    }
+
+    #[test]
+    fn test_no_articles() {
+        assert_eq!(
+            puzzle_description("<main><p>Please log in.</p></main>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_entities_and_escaping() {
+        let page = r#"<article class="day-desc"><p>Any symbol, like <code>*</code> or <code>#</code>, even 3 * 4 &amp; &lt;x&gt; &#39;quoted&#39;.</p><pre><code>467..114..
+...*......
+&lt;&gt;
+</code></pre></article>"#;
+        assert_eq!(
+            puzzle_description(page).unwrap(),
+            "Any symbol, like `*` or `#`, even 3 \\* 4 & <x> 'quoted'.\n\n```\n467..114..\n...*......\n<>\n```\n"
+        );
+    }
+}
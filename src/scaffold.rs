@@ -0,0 +1,318 @@
+//! `aoc new <day>`: turn one of the `cargo new` stub crates into a day laid out
+//! like day01-day05, and register it with the runner.
+//!
+//! the tests module starts out with the first example from
+//! `descriptions/dayN.md`, and its answer if the description gives one.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::examples;
+
+/// what `cargo new` left in `src/main.rs`
+const STUB_MAIN: &str = "fn main() {\n    println!(\"Hello, world!\");\n}";
+
+fn crate_dir(root: &Path, day: u8) -> PathBuf {
+    root.join("days").join(format!("day{day:02}"))
+}
+
+/// a day is a stub until it has a `lib.rs` or anything but hello world in `main.rs`
+pub fn is_stub(dir: &Path) -> bool {
+    let main = fs::read_to_string(dir.join("src").join("main.rs")).unwrap_or_default();
+    !dir.join("src").join("lib.rs").exists() && (main.is_empty() || main.trim() == STUB_MAIN)
+}
+
+pub fn cargo_toml(day: u8) -> String {
+    format!(
+        "[package]
+name = \"day{day:02}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = {{ path = \"../common\" }}
+"
+    )
+}
+
+pub fn main_rs(day: u8) -> String {
+    format!("common::main!(day{day:02}::Day{day:02});\n")
+}
+
+/// `text` as a raw string literal, with enough `#`s that it can't end early
+fn raw_string(text: &str) -> String {
+    let hashes = (0..)
+        .map(|n| "#".repeat(n))
+        .find(|h| !text.contains(&format!("\"{h}")))
+        .unwrap();
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+pub fn lib_rs(day: u8, example: Option<&examples::Example>) -> String {
+    let name = format!("Day{day:02}");
+    let mut out = format!(
+        "use std::convert::Infallible;
+
+use common::{{Solution, Unsolved}};
+
+pub struct {name};
+
+impl Solution for {name} {{
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Answer = Unsolved;
+    type Error = Infallible;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {{
+        Ok(lines.to_vec())
+    }}
+
+    fn part1(_input: &Self::Input) -> Self::Answer {{
+        Unsolved
+    }}
+
+    fn part2(_input: &Self::Input) -> Self::Answer {{
+        Unsolved
+    }}
+}}
+"
+    );
+    let Some(example) = example else {
+        return out;
+    };
+    let answer = match &example.part1 {
+        Some(answer) => answer.clone(),
+        None => "the example's answer".to_string(),
+    };
+    out += &format!(
+        "
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    static EXAMPLE: &str = {example};
+
+    fn example() -> Vec<String> {{
+        EXAMPLE.lines().map(String::from).collect()
+    }}
+
+    #[test]
+    fn test_pt1_example() {{
+        let answer = {name}::solve_part1(&example()).unwrap();
+        assert_eq!(answer.to_string(), {answer:?});
+    }}
+}}
+",
+        example = raw_string(example.input.trim_end_matches('\n')),
+    );
+    out
+}
+
+/// insert `new` among the lines that `day_of` recognises, keeping them in day
+/// order. if there are none yet it goes right after the line `anchor` starts.
+/// returns `None` when `new` is already there
+fn insert_day_line(
+    text: &str,
+    day: u8,
+    new: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+    anchor: &str,
+) -> Result<Option<String>, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&new) {
+        return Ok(None);
+    }
+    let after = lines
+        .iter()
+        .rposition(|l| day_of(l).is_some_and(|d| d < day))
+        .or_else(|| lines.iter().position(|l| l.starts_with(anchor)))
+        .ok_or(format!("no `{anchor}` line to add day {day} after"))?;
+    lines.insert(after + 1, new);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+/// the number in `dayNN` at the start of `s`
+fn leading_day(s: &str) -> Option<u8> {
+    s.strip_prefix("day")?.get(..2)?.parse().ok()
+}
+
+/// add the day to the root crate's dependencies and the runner's `DAYS`
+pub fn register(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let edits = [
+        (
+            root.join("Cargo.toml"),
+            format!("day{day:02} = {{ path = \"days/day{day:02}\" }}"),
+            "common = ",
+        ),
+        (
+            root.join("src").join("days.rs"),
+            format!("    Day::of::<day{day:02}::Day{day:02}>(),"),
+            "pub const DAYS",
+        ),
+    ];
+    let mut changed = vec![];
+    for (path, new, anchor) in edits {
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        let day_of = |l: &str| {
+            let l = l.trim_start();
+            leading_day(l).or_else(|| l.strip_prefix("Day::of::<").and_then(leading_day))
+        };
+        let updated = insert_day_line(&text, day, &new, day_of, anchor)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        if let Some(updated) = updated {
+            fs::write(&path, updated)
+                .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// scaffold `days/dayNN` under `root`, returning every file written
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let dir = crate_dir(root, day);
+    if !is_stub(&dir) {
+        return Err(format!(
+            "{} already has code in it, not overwriting",
+            dir.display()
+        ));
+    }
+    let description = root.join("descriptions").join(format!("day{day}.md"));
+    let example = fs::read_to_string(description)
+        .ok()
+        .and_then(|markdown| examples::extract(&markdown).into_iter().next());
+
+    let files = [
+        (dir.join("Cargo.toml"), cargo_toml(day)),
+        (dir.join("src").join("main.rs"), main_rs(day)),
+        (
+            dir.join("src").join("lib.rs"),
+            lib_rs(day, example.as_ref()),
+        ),
+    ];
+    let mut written = vec![];
+    for (path, contents) in files {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+        written.push(path);
+    }
+    written.extend(register(root, day)?);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const ROOT_CARGO: &str = "[package]
+name = \"aoc\"
+
+[dependencies]
+common = { path = \"days/common\" }
+day01 = { path = \"days/day01\" }
+day07 = { path = \"days/day07\" }
+serde = \"1\"
+";
+
+    const DAYS_RS: &str = "pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+    Day::of::<day07::Day07>(),
+];
+";
+
+    fn scratch(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("days/day06/src")).unwrap();
+        fs::create_dir_all(root.join("descriptions")).unwrap();
+        fs::write(root.join("Cargo.toml"), ROOT_CARGO).unwrap();
+        fs::write(root.join("src/days.rs"), DAYS_RS).unwrap();
+        fs::write(
+            root.join("days/day06/src/main.rs"),
+            format!("{STUB_MAIN}\n"),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_new_day_fills_in_stub() {
+        let root = scratch("fills");
+        fs::write(
+            root.join("descriptions/day6.md"),
+            "For example:\n\n```\nTime: \"7\"\n```\n\nThat makes **`288`**.\n",
+        )
+        .unwrap();
+
+        new_day(&root, 6).unwrap();
+        let lib = fs::read_to_string(root.join("days/day06/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day06;"));
+        assert!(lib.contains("const DAY: u8 = 6;"));
+        assert!(
+            lib.contains("static EXAMPLE: &str = r#\"Time: \"7\"\"#;"),
+            "{lib}"
+        );
+        assert!(lib.contains("assert_eq!(answer.to_string(), \"288\");"));
+        assert_eq!(
+            fs::read_to_string(root.join("days/day06/src/main.rs")).unwrap(),
+            "common::main!(day06::Day06);\n"
+        );
+        assert!(fs::read_to_string(root.join("days/day06/Cargo.toml"))
+            .unwrap()
+            .contains("common = { path = \"../common\" }"));
+
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            ROOT_CARGO.replace("day07 = ", "day06 = { path = \"days/day06\" }\nday07 = ")
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/days.rs")).unwrap(),
+            DAYS_RS.replace(
+                "    Day::of::<day07",
+                "    Day::of::<day06::Day06>(),\n    Day::of::<day07"
+            )
+        );
+
+        // it's real code now
+        let e = new_day(&root, 6).unwrap_err();
+        assert!(e.contains("already has code"), "{e}");
+    }
+
+    #[test]
+    fn test_new_day_without_description() {
+        let root = scratch("bare");
+        new_day(&root, 6).unwrap();
+        let lib = fs::read_to_string(root.join("days/day06/src/lib.rs")).unwrap();
+        assert!(!lib.contains("mod tests"));
+        // the runner's tests run this copy, see `days::tests`
+        assert_eq!(lib, include_str!("testdata/day06.rs"));
+    }
+
+    #[test]
+    fn test_refuses_real_code() {
+        let root = scratch("refuses");
+        fs::write(
+            root.join("days/day06/src/main.rs"),
+            "fn main() { solve(); }\n",
+        )
+        .unwrap();
+        assert!(new_day(&root, 6).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("days/day06/src/main.rs")).unwrap(),
+            "fn main() { solve(); }\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            ROOT_CARGO
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use std::convert::Infallible;
+
+use common::{Solution, Unsolved};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<String>;
+    type Answer = Unsolved;
+    type Error = Infallible;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, Self::Error> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(_input: &Self::Input) -> Self::Answer {
+        Unsolved
+    }
+
+    fn part2(_input: &Self::Input) -> Self::Answer {
+        Unsolved
+    }
+}